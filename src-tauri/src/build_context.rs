@@ -0,0 +1,280 @@
+// Montagem do contexto de build (tar) respeitando o .dockerignore
+use anyhow::{Context, Result};
+use std::path::Path;
+
+// Padrão do .dockerignore já normalizado
+struct IgnorePattern {
+    pattern: String,
+    negated: bool,
+}
+
+// Regras do .dockerignore: o último padrão que casar decide se o caminho é ignorado
+pub struct DockerIgnore {
+    patterns: Vec<IgnorePattern>,
+}
+
+impl DockerIgnore {
+    pub fn parse(content: &str) -> Self {
+        let patterns = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let (negated, pattern) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest.trim()),
+                    None => (false, line),
+                };
+                let pattern = normalize_pattern(pattern);
+                (!pattern.is_empty()).then_some(IgnorePattern { pattern, negated })
+            })
+            .collect();
+
+        DockerIgnore { patterns }
+    }
+
+    // Carrega o .dockerignore do diretório de contexto (se existir)
+    pub fn load(context_dir: &Path) -> Result<Self> {
+        let path = context_dir.join(".dockerignore");
+        if !path.exists() {
+            return Ok(DockerIgnore {
+                patterns: Vec::new(),
+            });
+        }
+
+        let content =
+            std::fs::read_to_string(&path).context(format!("Falha ao ler {}", path.display()))?;
+        Ok(DockerIgnore::parse(&content))
+    }
+
+    // Caminho relativo com "/"; um diretório ignorado também ignora tudo dentro dele
+    pub fn is_ignored(&self, relative_path: &str) -> bool {
+        let mut ignored = false;
+
+        for rule in &self.patterns {
+            let matches = path_prefixes(relative_path)
+                .any(|prefix| glob_match(rule.pattern.as_bytes(), prefix.as_bytes()));
+            if matches {
+                ignored = !rule.negated;
+            }
+        }
+
+        ignored
+    }
+
+    // Com exceções (!padrão), um diretório ignorado ainda pode ter arquivos incluídos
+    fn has_exceptions(&self) -> bool {
+        self.patterns.iter().any(|rule| rule.negated)
+    }
+}
+
+// Cria o tar do contexto em `destination` e devolve a quantidade de arquivos incluídos.
+// O Dockerfile e o .dockerignore sempre vão no contexto, mesmo se ignorados.
+pub fn create_build_context(
+    context_dir: &Path,
+    dockerfile: &str,
+    destination: &Path,
+) -> Result<usize> {
+    if !context_dir.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Diretório de contexto não encontrado: {}",
+            context_dir.display()
+        ));
+    }
+
+    let dockerfile = normalize_pattern(dockerfile);
+    if !context_dir.join(&dockerfile).is_file() {
+        return Err(anyhow::anyhow!(
+            "Dockerfile não encontrado no contexto: {}",
+            dockerfile
+        ));
+    }
+
+    let ignore = DockerIgnore::load(context_dir)?;
+    let file = std::fs::File::create(destination).context(format!(
+        "Falha ao criar arquivo temporário: {}",
+        destination.display()
+    ))?;
+    let mut builder = tar::Builder::new(file);
+    builder.follow_symlinks(false);
+
+    let mut count = 0;
+    append_directory(
+        &mut builder,
+        context_dir,
+        "",
+        &ignore,
+        &dockerfile,
+        &mut count,
+    )?;
+    builder
+        .into_inner()
+        .context("Falha ao finalizar contexto de build")?;
+
+    Ok(count)
+}
+
+fn append_directory(
+    builder: &mut tar::Builder<std::fs::File>,
+    directory: &Path,
+    relative: &str,
+    ignore: &DockerIgnore,
+    dockerfile: &str,
+    count: &mut usize,
+) -> Result<()> {
+    let mut entries: Vec<std::fs::DirEntry> = std::fs::read_dir(directory)
+        .context(format!("Falha ao ler diretório: {}", directory.display()))?
+        .collect::<std::io::Result<_>>()
+        .context(format!("Falha ao ler diretório: {}", directory.display()))?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let entry_relative = if relative.is_empty() {
+            name
+        } else {
+            format!("{}/{}", relative, name)
+        };
+        let file_type = entry
+            .file_type()
+            .context(format!("Falha ao ler {}", entry.path().display()))?;
+
+        let always_included = entry_relative == dockerfile || entry_relative == ".dockerignore";
+        let ignored = !always_included && ignore.is_ignored(&entry_relative);
+
+        if file_type.is_dir() {
+            if ignored && !ignore.has_exceptions() {
+                continue;
+            }
+            if !ignored {
+                builder
+                    .append_dir(&entry_relative, entry.path())
+                    .context(format!("Falha ao adicionar {}", entry_relative))?;
+            }
+            append_directory(
+                builder,
+                &entry.path(),
+                &entry_relative,
+                ignore,
+                dockerfile,
+                count,
+            )?;
+        } else if !ignored {
+            builder
+                .append_path_with_name(entry.path(), &entry_relative)
+                .context(format!("Falha ao adicionar {}", entry_relative))?;
+            *count += 1;
+        }
+    }
+
+    Ok(())
+}
+
+// Remove "./", "/" inicial e barras repetidas, como o Docker faz com filepath.Clean
+fn normalize_pattern(pattern: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in pattern.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
+// "a/b/c" -> "a", "a/b", "a/b/c"
+fn path_prefixes(path: &str) -> impl Iterator<Item = &str> {
+    path.match_indices('/')
+        .map(move |(index, _)| &path[..index])
+        .chain(std::iter::once(path))
+}
+
+// Glob do .dockerignore: `*` e `?` não cruzam "/", `**` cruza, `[...]` e `\` funcionam como no Go
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(b'*') if pattern.get(1) == Some(&b'*') => {
+            // "**/" também casa com zero diretórios
+            let rest = &pattern[2..];
+            let rest_after_slash = rest.strip_prefix(b"/").unwrap_or(rest);
+            if glob_match(rest_after_slash, text) {
+                return true;
+            }
+            (0..=text.len()).any(|index| glob_match(rest, &text[index..]))
+        }
+        Some(b'*') => {
+            let rest = &pattern[1..];
+            if glob_match(rest, text) {
+                return true;
+            }
+            for index in 0..text.len() {
+                if text[index] == b'/' {
+                    break;
+                }
+                if glob_match(rest, &text[index + 1..]) {
+                    return true;
+                }
+            }
+            false
+        }
+        Some(b'?') => {
+            matches!(text.first(), Some(c) if *c != b'/') && glob_match(&pattern[1..], &text[1..])
+        }
+        Some(b'[') => {
+            let Some((&c, text_rest)) = text.split_first() else {
+                return false;
+            };
+            match match_class(&pattern[1..], c) {
+                Some((true, rest)) => glob_match(rest, text_rest),
+                _ => false,
+            }
+        }
+        Some(b'\\') if pattern.len() > 1 => {
+            text.first() == Some(&pattern[1]) && glob_match(&pattern[2..], &text[1..])
+        }
+        Some(c) => text.first() == Some(c) && glob_match(&pattern[1..], &text[1..]),
+    }
+}
+
+// Avalia uma classe "[...]" (com "^" ou "!" para negação) e devolve o restante do padrão
+fn match_class(pattern: &[u8], c: u8) -> Option<(bool, &[u8])> {
+    let (negated, mut pattern) = match pattern.first() {
+        Some(b'^' | b'!') => (true, &pattern[1..]),
+        _ => (false, pattern),
+    };
+
+    let mut matched = false;
+    let mut first = true;
+    loop {
+        let (&start, rest) = pattern.split_first()?;
+        if start == b']' && !first {
+            pattern = rest;
+            break;
+        }
+        first = false;
+
+        let (start, rest) = if start == b'\\' {
+            let (&escaped, rest) = rest.split_first()?;
+            (escaped, rest)
+        } else {
+            (start, rest)
+        };
+
+        if rest.first() == Some(&b'-') && rest.get(1).is_some_and(|end| *end != b']') {
+            let end = rest[1];
+            if start <= c && c <= end {
+                matched = true;
+            }
+            pattern = &rest[2..];
+        } else {
+            if start == c {
+                matched = true;
+            }
+            pattern = rest;
+        }
+    }
+
+    Some((matched != negated && c != b'/', pattern))
+}
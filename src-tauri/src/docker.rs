@@ -1,6 +1,12 @@
 // Imports para gerenciamento do Docker
+use crate::build_context::create_build_context;
+use crate::docker_run::{generate_snippets, is_named_volume, ContainerRunSnippets};
+use crate::environment::{mask_env_vars, mask_secret_assignments};
+use crate::host_ports;
+use crate::registry_auth::with_credential_store;
 use anyhow::{Context, Result};
 use bollard::{
+    auth::DockerCredentials,
     models::ContainerCreateBody,
     models::{ContainerStatsResponse, ImageSummary},
     query_parameters::CreateContainerOptions,
@@ -14,11 +20,13 @@ use chrono;
 use futures_util::TryStreamExt;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
+    path::{Component, Path, PathBuf},
     process::Command,
     time::{SystemTime, UNIX_EPOCH},
 };
+use tokio_util::sync::CancellationToken;
 
 // Informações básicas de um container
 #[derive(Debug, Serialize, Deserialize)]
@@ -30,6 +38,7 @@ pub struct ContainerInfo {
     pub status: String,
     pub ports: Vec<i32>,
     pub created: i64,
+    pub health: Option<String>, // starting, healthy ou unhealthy (se houver healthcheck)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -161,14 +170,66 @@ pub struct CreateContainerRequest {
     pub environment: Vec<EnvVar>,
     pub command: Option<String>,
     pub restart_policy: String,
+    pub network: Option<String>,
+    pub auto_remove: Option<bool>, // equivalente ao --rm
 }
 
 // Mapeamento de portas
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PortMapping {
-    pub host_port: u16,
+    pub host_ip: Option<String>,    // padrão 0.0.0.0 (ex.: 127.0.0.1 ou ::1)
+    pub host_port: Option<u16>,     // vazio = porta aleatória escolhida pelo Docker
+    pub host_port_end: Option<u16>, // fim do intervalo no host
+    pub container_port: u16,
+    pub container_port_end: Option<u16>, // fim do intervalo no container
+    pub protocol: String,                // tcp, udp ou sctp
+}
+
+impl PortMapping {
+    // Portas do container cobertas pelo mapeamento
+    pub fn container_ports(&self) -> Vec<u16> {
+        let end = self.container_port_end.unwrap_or(self.container_port);
+        (self.container_port..=end).collect()
+    }
+
+    // Portas fixas do host que serão usadas (intervalo do host para uma porta só é decidido pelo Docker)
+    pub fn fixed_host_ports(&self) -> Vec<u16> {
+        match (self.host_port, self.host_port_end) {
+            (Some(start), Some(end)) if self.container_port_end.is_some() => {
+                (start..=end).collect()
+            }
+            (Some(start), None) => vec![start],
+            _ => Vec::new(),
+        }
+    }
+
+    // Valor de HostPort para a n-ésima porta do container
+    fn host_port_binding(&self, index: u16) -> String {
+        match (self.host_port, self.host_port_end) {
+            (Some(start), Some(_)) if self.container_port_end.is_some() => {
+                (start + index).to_string()
+            }
+            (Some(start), Some(end)) => format!("{}-{}", start, end),
+            (Some(start), None) => start.to_string(),
+            (None, _) => String::new(),
+        }
+    }
+}
+
+// Porta efetivamente publicada após a criação do container
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssignedPort {
     pub container_port: u16,
-    pub protocol: String, // tcp ou udp
+    pub protocol: String,
+    pub host_ip: String,
+    pub host_port: u16,
+}
+
+// Resultado da criação de um container
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateContainerResult {
+    pub container_id: String,
+    pub ports: Vec<AssignedPort>,
 }
 
 // Mapeamento de volumes
@@ -186,6 +247,466 @@ pub struct EnvVar {
     pub value: String,
 }
 
+// Entrada de um diretório dentro do container
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContainerFileEntry {
+    pub name: String,
+    pub path: String,
+    pub kind: String, // file, directory, symlink ou other
+    pub size: u64,
+    pub mode: u32,
+    pub modified: i64,
+    pub link_target: Option<String>,
+}
+
+// Progresso de uma transferência de arquivos entre host e container
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransferProgress {
+    pub container_id: String,
+    pub path: String,
+    pub direction: String, // download ou upload
+    pub bytes_transferred: u64,
+    pub total_bytes: Option<u64>,
+    pub done: bool,
+}
+
+// Intervalo mínimo (em bytes) entre eventos de progresso de transferência
+const TRANSFER_PROGRESS_STEP: u64 = 1024 * 1024;
+
+// Processo em execução dentro de um container (saída do `docker top`)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContainerProcess {
+    pub pid: String,
+    pub container_pid: Option<String>, // PID no namespace do container (só com daemon local)
+    pub user: String,
+    pub cpu_percentage: Option<f64>,
+    pub memory_percentage: Option<f64>,
+    pub command: String,
+    pub values: Vec<String>,
+}
+
+// Resultado do `docker top` com as colunas originais do ps
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContainerTop {
+    pub titles: Vec<String>,
+    pub processes: Vec<ContainerProcess>,
+}
+
+// Nó da árvore de alterações do sistema de arquivos do container
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileChangeNode {
+    pub name: String,
+    pub path: String,
+    pub kind: Option<String>, // added, modified ou deleted; None para diretórios intermediários
+    pub size: Option<u64>,
+    pub children: Vec<FileChangeNode>,
+}
+
+// Alterações do container em relação à imagem
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContainerChanges {
+    pub added: Vec<String>,
+    pub modified: Vec<String>,
+    pub deleted: Vec<String>,
+    pub tree: Vec<FileChangeNode>,
+    pub total_size: Option<u64>,
+}
+
+// Estrutura para gerar uma imagem a partir de um container
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommitContainerRequest {
+    pub container_id: String,
+    pub repo: String,
+    pub tag: Option<String>,
+    pub author: Option<String>,
+    pub message: Option<String>,
+    pub pause: bool,
+    pub changes: Vec<String>, // instruções no formato Dockerfile (ENV, CMD, EXPOSE...)
+}
+
+// Novos limites de recursos e política de restart para um container em execução
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct UpdateContainerRequest {
+    pub memory: Option<i64>,      // bytes
+    pub memory_swap: Option<i64>, // bytes (memória + swap); -1 para ilimitado
+    pub cpu_shares: Option<i64>,
+    pub cpu_quota: Option<i64>, // microssegundos por período; -1 para ilimitado
+    pub cpu_period: Option<i64>, // microssegundos
+    pub cpuset_cpus: Option<String>,
+    pub pids_limit: Option<i64>, // -1 para ilimitado
+    pub restart_policy: Option<String>,
+    pub restart_max_retries: Option<i64>,
+}
+
+// Ação aplicada a vários containers de uma vez
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkActionRequest {
+    pub container_ids: Vec<String>,
+    pub label_selector: Option<String>, // "chave=valor" ou apenas "chave"
+    pub action: String,                 // start, stop, restart, pause, unpause ou remove
+    pub concurrency: Option<usize>,
+    pub force: Option<bool>, // usado apenas em remove
+}
+
+// Resultado da ação para um container
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkActionResult {
+    pub container_id: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+// Progresso de uma ação em lote
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BulkActionProgress {
+    pub action: String,
+    pub completed: usize,
+    pub total: usize,
+    pub result: BulkActionResult,
+}
+
+// Paralelismo padrão e máximo das ações em lote
+const BULK_DEFAULT_CONCURRENCY: usize = 4;
+const BULK_MAX_CONCURRENCY: usize = 16;
+
+// Recriação de um container preservando a configuração
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecreateContainerRequest {
+    pub container_id: String,
+    pub image: Option<String>, // nova referência de imagem; padrão: a mesma do container
+    pub pull: bool,
+    pub keep_old: bool,
+}
+
+// Resultado da recriação
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecreateContainerResult {
+    pub container_id: String,
+    pub old_container_id: String,
+    pub image: String,
+    pub image_id: String,
+    pub image_updated: bool,
+    pub old_container_removed: bool,
+}
+
+// Porta do host indisponível: publicada por um container, ocupada no host ou repetida
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PortConflict {
+    pub host_port: u16,
+    pub protocol: String,
+    pub source: String,          // container, host ou request
+    pub used_by: Option<String>, // nome do container, quando houver
+    pub suggested_port: Option<u16>,
+}
+
+// Resultado da verificação prévia das portas de um container
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PortCheckResult {
+    pub available: bool,
+    pub conflicts: Vec<PortConflict>,
+    // Portas ocupadas por processos desta máquina; só um aviso, pois o daemon pode estar
+    // em outro namespace de rede (Docker Desktop, DOCKER_HOST remoto, rootless)
+    pub host_warnings: Vec<PortConflict>,
+}
+
+// Porta do host em uso ou pedida, com o IP de escuta (vazio = todas as interfaces)
+#[derive(Debug, Clone)]
+struct HostPortBinding {
+    host_ip: String,
+    host_port: u16,
+    protocol: String,
+}
+
+impl HostPortBinding {
+    // Mesma porta e protocolo, no mesmo IP ou com algum dos lados em 0.0.0.0/::
+    fn collides_with(&self, other: &HostPortBinding) -> bool {
+        self.host_port == other.host_port
+            && self.protocol == other.protocol
+            && (is_wildcard_ip(&self.host_ip)
+                || is_wildcard_ip(&other.host_ip)
+                || same_ip(&self.host_ip, &other.host_ip))
+    }
+}
+
+// Quantas portas seguintes são testadas ao procurar uma porta livre
+const PORT_SEARCH_LIMIT: u16 = 1000;
+
+// Container de tarefa única (migração, seed) executado até terminar
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RunTaskRequest {
+    pub container: CreateContainerRequest,
+    pub remove: bool,
+    pub tail_lines: Option<usize>,
+}
+
+// Linha de log emitida durante a execução da tarefa
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskLogLine {
+    pub container_id: String,
+    pub stream: String, // stdout ou stderr
+    pub line: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RunTaskResult {
+    pub container_id: String,
+    pub exit_code: i64,
+    pub success: bool,
+    pub duration_ms: u64,
+    pub log_tail: Vec<String>,
+    pub removed: bool,
+}
+
+// Linhas finais de log devolvidas por padrão ao fim da tarefa
+const TASK_DEFAULT_TAIL_LINES: usize = 100;
+
+// Build de imagem a partir de um diretório de contexto
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildImageRequest {
+    pub context_dir: String,
+    pub dockerfile: Option<String>, // relativo ao contexto (padrão: Dockerfile)
+    pub tag: Option<String>,
+    pub build_args: Option<HashMap<String, String>>,
+    pub target: Option<String>,
+    pub no_cache: bool,
+    pub pull: bool,
+}
+
+// Linha do log de build emitida durante o processo
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BuildLogLine {
+    pub line: String,
+    pub step: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuildImageResult {
+    pub image_id: String,
+    pub tag: Option<String>,
+    pub context_files: usize,
+}
+
+// Credenciais de registry informadas pelo usuário
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RegistryCredentials {
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub server_address: Option<String>,
+    pub identity_token: Option<String>,
+}
+
+impl RegistryCredentials {
+    fn to_docker_credentials(&self) -> DockerCredentials {
+        DockerCredentials {
+            username: self.username.clone(),
+            password: self.password.clone(),
+            serveraddress: self.server_address.clone(),
+            identitytoken: self.identity_token.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+// Progresso do push de uma imagem
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImagePushProgress {
+    pub image: String,
+    pub status: String,
+    pub progress: Option<String>,
+    pub current: Option<i64>,
+    pub total: Option<i64>,
+}
+
+// Situação de uma tag local em relação ao registry
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageUpdateStatus {
+    pub image: String,
+    pub image_id: String,
+    pub local_digest: Option<String>,
+    pub remote_digest: Option<String>,
+    pub update_available: bool,
+    pub error: Option<String>, // registry inacessível, imagem sem digest etc.
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContainerUpdateStatus {
+    pub container_id: String,
+    pub container_name: String,
+    pub image: String,
+    pub update_available: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageUpdateReport {
+    pub images: Vec<ImageUpdateStatus>,
+    pub containers: Vec<ContainerUpdateStatus>,
+}
+
+// Progresso de gravação/carga de um arquivo tar de imagens
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImageArchiveProgress {
+    pub operation_id: String,
+    pub path: String,
+    pub direction: String, // save ou load
+    pub bytes_transferred: u64,
+    pub total_bytes: Option<u64>,
+    pub done: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LoadImagesResult {
+    pub loaded: Vec<String>, // tags (ou ids, para imagens sem tag)
+    pub bytes_read: u64,
+}
+
+// Camada do histórico de uma imagem
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageLayer {
+    pub id: String, // "<missing>" para camadas vindas de outro host
+    pub created: i64,
+    pub instruction: String,
+    pub created_by: String,
+    pub size: i64,
+    pub size_percent: f64,
+    pub empty_layer: bool,
+    pub tags: Vec<String>,
+    pub comment: String,
+}
+
+// Container que impede a remoção de uma imagem
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageBlockingContainer {
+    pub id: String,
+    pub name: String,
+    pub state: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RemoveImageResult {
+    pub removed: bool,
+    pub untagged: Vec<String>,
+    pub deleted: Vec<String>,
+    // Preenchido quando a remoção falha por conflito
+    pub blocking_containers: Vec<ImageBlockingContainer>,
+}
+
+// Limite de `older_than_hours` (100 anos)
+const MAX_PRUNE_AGE_HOURS: u64 = 100 * 365 * 24;
+
+// Critérios de limpeza de imagens
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PruneImagesRequest {
+    pub all: bool, // false = somente imagens sem tag (dangling)
+    pub older_than_hours: Option<u64>,
+    pub labels: Vec<String>, // "chave", "chave=valor"; "!" no início exclui
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PrunableImage {
+    pub id: String,
+    pub tags: Vec<String>,
+    pub created: i64,
+    pub size: u64, // espaço não compartilhado com outras imagens
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PruneImagesPreview {
+    pub images: Vec<PrunableImage>,
+    pub reclaimable_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PruneImagesResult {
+    pub deleted: Vec<String>,
+    pub untagged: Vec<String>,
+    pub reclaimed_bytes: u64,
+}
+
+// Detalhes completos de uma imagem
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageDetails {
+    pub id: String,
+    pub repo_tags: Vec<String>,
+    pub repo_digests: Vec<String>,
+    pub parent: String,
+    pub created: String,
+    pub author: String,
+    pub architecture: String,
+    pub os: String,
+    pub variant: Option<String>,
+    pub size: i64,
+    pub labels: HashMap<String, String>,
+    pub env: Vec<EnvVar>,
+    pub entrypoint: Vec<String>,
+    pub cmd: Vec<String>,
+    pub working_dir: String,
+    pub user: String,
+    pub exposed_ports: Vec<String>,
+    pub volumes: Vec<String>,
+    pub layers: Vec<ImageLayer>,
+}
+
+// Resultado de uma execução do healthcheck
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthProbe {
+    pub start: String,
+    pub end: String,
+    pub exit_code: i64,
+    pub output: String,
+}
+
+// Estado do healthcheck de um container
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContainerHealth {
+    pub container_id: String,
+    pub status: String, // none, starting, healthy ou unhealthy
+    pub failing_streak: i64,
+    pub probes: Vec<HealthProbe>,
+}
+
+// Mudança de saúde informada pelos eventos do Docker
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HealthChangeEvent {
+    pub container_id: String,
+    pub name: String,
+    pub status: String,
+    pub time: i64,
+}
+
+// Quantas saídas do healthcheck são retornadas por padrão
+const DEFAULT_HEALTH_PROBES: usize = 5;
+
+// Limites efetivos do HostConfig de um container
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContainerResources {
+    pub memory: i64,
+    pub memory_swap: i64,
+    pub cpu_shares: i64,
+    pub cpu_quota: i64,
+    pub cpu_period: i64,
+    pub nano_cpus: i64,
+    pub cpuset_cpus: String,
+    pub pids_limit: i64,
+    pub restart_policy: String,
+    pub restart_max_retries: i64,
+}
+
+// Memória mínima aceita pelo Docker para um container (6 MB)
+const MIN_CONTAINER_MEMORY: i64 = 6 * 1024 * 1024;
+
+// Instruções de Dockerfile aceitas pelo commit
+const COMMIT_CHANGE_INSTRUCTIONS: &[&str] = &[
+    "CMD",
+    "ENTRYPOINT",
+    "ENV",
+    "EXPOSE",
+    "LABEL",
+    "ONBUILD",
+    "USER",
+    "VOLUME",
+    "WORKDIR",
+];
+
 impl DockerManager {
     // Cria nova instância conectando ao Docker daemon
     pub async fn new() -> Result<Self> {
@@ -270,6 +791,21 @@ impl DockerManager {
         })
     }
 
+    // Plataforma do daemon no formato dos manifestos ("linux/amd64", "linux/arm64")
+    pub async fn daemon_platform(&self) -> Result<String> {
+        let version = self
+            .docker
+            .version()
+            .await
+            .context("Falha ao obter versão do Docker")?;
+
+        Ok(format!(
+            "{}/{}",
+            version.os.unwrap_or_else(|| "linux".to_string()),
+            version.arch.unwrap_or_else(|| "amd64".to_string())
+        ))
+    }
+
     // Lista todos os containers (ativos e parados)
     pub async fn list_containers(&self) -> Result<Vec<ContainerInfo>> {
         let containers = self
@@ -295,6 +831,7 @@ impl DockerManager {
                 state: container
                     .state
                     .map_or("unknown".to_string(), |s| s.to_string()),
+                health: container.status.as_deref().and_then(health_from_status),
                 status: container.status.unwrap_or_default(),
                 ports: container
                     .ports
@@ -336,6 +873,7 @@ impl DockerManager {
                     state: container
                         .state
                         .map_or("unknown".to_string(), |s| s.to_string()),
+                    health: container.status.as_deref().and_then(health_from_status),
                     status: container.status.unwrap_or_default(),
                     ports: container
                         .ports
@@ -356,18 +894,12 @@ impl DockerManager {
 
     // Inicia um container
     pub async fn start_container(&self, container_name: &str) -> Result<()> {
-        let output = Command::new("docker")
-            .args(&["start", container_name])
-            .output()
-            .context("Failed to execute docker start command")?;
+        use bollard::query_parameters::StartContainerOptions;
 
-        if !output.status.success() {
-            return Err(anyhow::anyhow!(
-                "Failed to start container {}: {}",
-                container_name,
-                String::from_utf8_lossy(&output.stderr)
-            ));
-        }
+        self.docker
+            .start_container(container_name, None::<StartContainerOptions>)
+            .await
+            .map_err(|e| anyhow::anyhow!("Falha ao iniciar container {}: {}", container_name, e))?;
 
         Ok(())
     }
@@ -418,39 +950,61 @@ impl DockerManager {
     }
 
     // deleta uma imagem
-    pub async fn remove_image(&self, image_id: &str) -> Result<()> {
-        let output = Command::new("docker")
-            .args(&["rmi", image_id])
-            .output()
-            .context("Failed to execute docker rmi command")?;
+    pub async fn remove_image(
+        &self,
+        image: &str,
+        force: bool,
+        noprune: bool,
+    ) -> Result<RemoveImageResult> {
+        use bollard::query_parameters::RemoveImageOptions;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr).to_lowercase();
-            if stderr.contains("conflict")
-                && stderr.contains("image is being used by running container")
-            {
-                return Err(anyhow::anyhow!(
-                    "IN_USE:A imagem está em uso por um contêiner."
-                ));
-            } else {
-                return Err(anyhow::anyhow!(
-                    "OTHER_ERROR:Não foi possível remover a imagem. Tente forçar a remoção."
-                ));
+        // Removendo por tag, o Docker só remove a tag se a imagem tiver outras
+        let response = self
+            .docker
+            .remove_image(image, Some(RemoveImageOptions { force, noprune }), None)
+            .await;
+
+        match response {
+            Ok(items) => Ok(RemoveImageResult {
+                removed: true,
+                untagged: items
+                    .iter()
+                    .filter_map(|item| item.untagged.clone())
+                    .collect(),
+                deleted: items.into_iter().filter_map(|item| item.deleted).collect(),
+                blocking_containers: Vec::new(),
+            }),
+            Err(bollard::errors::Error::DockerResponseServerError {
+                status_code: 409,
+                message,
+            }) => {
+                let blocking_containers = self.image_containers(image).await?;
+                if blocking_containers.is_empty() {
+                    return Err(anyhow::anyhow!(
+                        "Não foi possível remover a imagem: {}",
+                        message
+                    ));
+                }
+                Ok(RemoveImageResult {
+                    removed: false,
+                    untagged: Vec::new(),
+                    deleted: Vec::new(),
+                    blocking_containers,
+                })
             }
+            Err(e) => Err(anyhow::anyhow!("Falha ao remover imagem {}: {}", image, e)),
         }
-
-        Ok(())
     }
 
-    // Lista todas as networks
-    pub async fn list_networks(&self) -> Result<Vec<NetworkInfo>> {
-        let networks = self
+    // Containers (em execução ou parados) criados a partir da imagem
+    async fn image_containers(&self, image: &str) -> Result<Vec<ImageBlockingContainer>> {
+        let image_id = self
             .docker
-            .list_networks(Some(ListNetworksOptions {
-                ..Default::default()
-            }))
+            .inspect_image(image)
             .await
-            .context("Falha ao listar networks")?;
+            .context(format!("Falha ao inspecionar imagem: {}", image))?
+            .id
+            .unwrap_or_default();
 
         let containers = self
             .docker
@@ -461,25 +1015,167 @@ impl DockerManager {
             .await
             .context("Falha ao listar containers")?;
 
-        let network_ids: Vec<String> = containers
+        Ok(containers
             .into_iter()
-            .flat_map(|container| {
-                container
-                    .network_settings
-                    .and_then(|settings| settings.networks)
-                    .unwrap_or_default()
-                    .into_values()
-                    .filter_map(|endpoint| endpoint.network_id)
+            .filter(|container| container.image_id.as_deref() == Some(image_id.as_str()))
+            .map(|container| ImageBlockingContainer {
+                id: container.id.unwrap_or_default(),
+                name: container
+                    .names
+                    .and_then(|names| names.into_iter().next())
+                    .map(|name| name.trim_start_matches('/').to_string())
+                    .unwrap_or_default(),
+                state: container
+                    .state
+                    .map(|state| state.to_string())
+                    .unwrap_or_default(),
             })
-            .collect();
+            .collect())
+    }
 
-        let mut network_infos: Vec<NetworkInfo> = networks
-            .into_iter()
-            .filter_map(|network| {
-                let network_name = network.name.as_deref().unwrap_or("");
-                let id = network.id.unwrap_or_default();
+    // Mostra o que o prune removeria, com os mesmos critérios do daemon
+    pub async fn preview_prune_images(
+        &self,
+        request: &PruneImagesRequest,
+    ) -> Result<PruneImagesPreview> {
+        let images = self
+            .docker
+            .list_images(Some(ListImagesOptions {
+                all: false,
+                shared_size: true,
+                ..Default::default()
+            }))
+            .await
+            .context("Falha ao listar imagens")?;
 
-                // Filtra networks de sistema (bridge, host, none)
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                ..Default::default()
+            }))
+            .await
+            .context("Falha ao listar containers")?;
+        let used_images: HashSet<String> = containers
+            .into_iter()
+            .filter_map(|container| container.image_id)
+            .collect();
+
+        let cutoff = validate_prune_age(request.older_than_hours)?
+            .map(|hours| chrono::Utc::now().timestamp() - hours as i64 * 3600);
+
+        let prunable: Vec<PrunableImage> = images
+            .into_iter()
+            .filter(|image| !used_images.contains(&image.id))
+            .filter(|image| request.all || image.repo_tags.iter().all(|tag| tag == "<none>:<none>"))
+            .filter(|image| cutoff.is_none_or(|cutoff| image.created < cutoff))
+            .filter(|image| {
+                request
+                    .labels
+                    .iter()
+                    .all(|filter| label_filter_matches(&image.labels, filter))
+            })
+            .map(|image| PrunableImage {
+                size: if image.shared_size >= 0 {
+                    (image.size - image.shared_size).max(0) as u64
+                } else {
+                    image.size.max(0) as u64
+                },
+                tags: image
+                    .repo_tags
+                    .into_iter()
+                    .filter(|tag| tag != "<none>:<none>")
+                    .collect(),
+                id: image.id,
+                created: image.created,
+            })
+            .collect();
+
+        Ok(PruneImagesPreview {
+            reclaimable_bytes: prunable.iter().map(|image| image.size).sum(),
+            images: prunable,
+        })
+    }
+
+    // Executa o prune e devolve o espaço efetivamente liberado
+    pub async fn prune_images(&self, request: &PruneImagesRequest) -> Result<PruneImagesResult> {
+        use bollard::query_parameters::PruneImagesOptions;
+
+        let mut filters: HashMap<String, Vec<String>> = HashMap::new();
+        filters.insert("dangling".to_string(), vec![(!request.all).to_string()]);
+        if let Some(hours) = validate_prune_age(request.older_than_hours)? {
+            filters.insert("until".to_string(), vec![format!("{}h", hours)]);
+        }
+        for label in &request.labels {
+            match label.strip_prefix('!') {
+                Some(excluded) => filters
+                    .entry("label!".to_string())
+                    .or_default()
+                    .push(excluded.to_string()),
+                None => filters
+                    .entry("label".to_string())
+                    .or_default()
+                    .push(label.to_string()),
+            }
+        }
+
+        let response = self
+            .docker
+            .prune_images(Some(PruneImagesOptions {
+                filters: Some(filters),
+            }))
+            .await
+            .context("Falha ao remover imagens não utilizadas")?;
+
+        let items = response.images_deleted.unwrap_or_default();
+        Ok(PruneImagesResult {
+            untagged: items
+                .iter()
+                .filter_map(|item| item.untagged.clone())
+                .collect(),
+            deleted: items.into_iter().filter_map(|item| item.deleted).collect(),
+            reclaimed_bytes: response.space_reclaimed.unwrap_or_default().max(0) as u64,
+        })
+    }
+
+    // Lista todas as networks
+    pub async fn list_networks(&self) -> Result<Vec<NetworkInfo>> {
+        let networks = self
+            .docker
+            .list_networks(Some(ListNetworksOptions {
+                ..Default::default()
+            }))
+            .await
+            .context("Falha ao listar networks")?;
+
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                ..Default::default()
+            }))
+            .await
+            .context("Falha ao listar containers")?;
+
+        let network_ids: Vec<String> = containers
+            .into_iter()
+            .flat_map(|container| {
+                container
+                    .network_settings
+                    .and_then(|settings| settings.networks)
+                    .unwrap_or_default()
+                    .into_values()
+                    .filter_map(|endpoint| endpoint.network_id)
+            })
+            .collect();
+
+        let mut network_infos: Vec<NetworkInfo> = networks
+            .into_iter()
+            .filter_map(|network| {
+                let network_name = network.name.as_deref().unwrap_or("");
+                let id = network.id.unwrap_or_default();
+
+                // Filtra networks de sistema (bridge, host, none)
                 let is_system = matches!(network_name, "bridge" | "host" | "none");
 
                 if is_system {
@@ -663,75 +1359,123 @@ impl DockerManager {
         Ok(())
     }
 
-    // Para um container
-    pub async fn stop_container(&self, container_name: &str) -> Result<()> {
-        let output = Command::new("docker")
-            .args(&["stop", container_name])
-            .output()
-            .context("Failed to execute docker stop command")?;
+    // Para um container, aguardando `timeout` segundos antes de forçar (padrão do Docker: 10s)
+    pub async fn stop_container(&self, container_name: &str, timeout: Option<i32>) -> Result<()> {
+        use bollard::query_parameters::StopContainerOptions;
 
-        if !output.status.success() {
-            return Err(anyhow::anyhow!(
-                "Failed to stop container {}: {}",
+        self.docker
+            .stop_container(
                 container_name,
-                String::from_utf8_lossy(&output.stderr)
-            ));
-        }
+                Some(StopContainerOptions {
+                    t: validate_stop_timeout(timeout)?,
+                    signal: None,
+                }),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Falha ao parar container {}: {}", container_name, e))?;
 
         Ok(())
     }
 
     // Pausa um container
     pub async fn pause_container(&self, container_name: &str) -> Result<()> {
-        let output = Command::new("docker")
-            .args(&["pause", container_name])
-            .output()
-            .context("Failed to execute docker pause command")?;
-
-        if !output.status.success() {
-            return Err(anyhow::anyhow!(
-                "Failed to pause container {}: {}",
-                container_name,
-                String::from_utf8_lossy(&output.stderr)
-            ));
-        }
+        self.docker
+            .pause_container(container_name)
+            .await
+            .map_err(|e| anyhow::anyhow!("Falha ao pausar container {}: {}", container_name, e))?;
 
         Ok(())
     }
 
     // Despausa um container
     pub async fn unpause_container(&self, container_name: &str) -> Result<()> {
-        let output = Command::new("docker")
-            .args(&["unpause", container_name])
-            .output()
-            .context("Failed to execute docker unpause command")?;
+        self.docker
+            .unpause_container(container_name)
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!("Falha ao despausar container {}: {}", container_name, e)
+            })?;
 
-        if !output.status.success() {
-            return Err(anyhow::anyhow!(
-                "Failed to unpause container {}: {}",
+        Ok(())
+    }
+
+    // deleta um container, opcionalmente forçando e removendo volumes anônimos
+    pub async fn remove_container(
+        &self,
+        container_name: &str,
+        force: bool,
+        remove_volumes: bool,
+    ) -> Result<()> {
+        use bollard::query_parameters::RemoveContainerOptions;
+
+        self.docker
+            .remove_container(
                 container_name,
-                String::from_utf8_lossy(&output.stderr)
-            ));
-        }
+                Some(RemoveContainerOptions {
+                    v: remove_volumes,
+                    force,
+                    link: false,
+                }),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Falha ao remover container {}: {}", container_name, e))?;
 
         Ok(())
     }
 
-    // deleta um container
-    pub async fn remove_container(&self, container_name: &str) -> Result<()> {
-        let output = Command::new("docker")
-            .args(&["rm", container_name])
-            .output()
-            .context("Failed to execute docker unpause command")?;
+    // Renomeia um container
+    pub async fn rename_container(&self, container_id: &str, new_name: &str) -> Result<()> {
+        use bollard::query_parameters::RenameContainerOptions;
 
-        if !output.status.success() {
+        let new_name = new_name.trim().trim_start_matches('/');
+        if !is_valid_container_name(new_name) {
             return Err(anyhow::anyhow!(
-                "Failed to unpause container {}: {}",
-                container_name,
-                String::from_utf8_lossy(&output.stderr)
+                "Nome inválido: use letras, números, '_', '.' ou '-'"
+            ));
+        }
+
+        if self.container_name_exists(new_name).await? {
+            return Err(anyhow::anyhow!(
+                "Container com nome '{}' já existe",
+                new_name
             ));
         }
 
+        self.docker
+            .rename_container(
+                container_id,
+                RenameContainerOptions {
+                    name: new_name.to_string(),
+                },
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Falha ao renomear container {}: {}", container_id, e))?;
+
+        Ok(())
+    }
+
+    // Envia um sinal ao processo principal do container (docker kill --signal)
+    pub async fn kill_container(&self, container_id: &str, signal: &str) -> Result<()> {
+        use bollard::query_parameters::KillContainerOptions;
+
+        let signal = normalize_signal(signal)?;
+        self.docker
+            .kill_container(
+                container_id,
+                Some(KillContainerOptions {
+                    signal: format!("SIG{}", signal),
+                }),
+            )
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "Falha ao enviar SIG{} ao container {}: {}",
+                    signal,
+                    container_id,
+                    e
+                )
+            })?;
+
         Ok(())
     }
 
@@ -760,6 +1504,7 @@ impl DockerManager {
                 state: container
                     .state
                     .map_or("unknown".to_string(), |s| s.to_string()),
+                health: container.status.as_deref().and_then(health_from_status),
                 status: container.status.unwrap_or_default(),
                 ports: container
                     .ports
@@ -1026,9 +1771,15 @@ impl DockerManager {
         }
     }
 
-    pub async fn restart_container(&self, container_id: &str) -> Result<()> {
+    pub async fn restart_container(&self, container_id: &str, timeout: Option<i32>) -> Result<()> {
         self.docker
-            .restart_container(container_id, None::<RestartContainerOptions>)
+            .restart_container(
+                container_id,
+                Some(RestartContainerOptions {
+                    t: validate_stop_timeout(timeout)?,
+                    signal: None,
+                }),
+            )
             .await
             .context(format!("Falha ao reiniciar container: {}", container_id))?;
 
@@ -1244,7 +1995,32 @@ impl DockerManager {
     }
 
     // Cria um novo container
-    pub async fn create_container(&self, request: CreateContainerRequest) -> Result<String> {
+    pub async fn create_container(
+        &self,
+        request: CreateContainerRequest,
+    ) -> Result<CreateContainerResult> {
+        let container_id = self.create_container_stopped(&request).await?;
+
+        // Inicia o container automaticamente
+        self.start_container(&container_id)
+            .await
+            .context("Container criado mas falha ao iniciar")?;
+
+        // Portas aleatórias só são conhecidas depois que o container inicia
+        let ports = if request.ports.is_empty() {
+            Vec::new()
+        } else {
+            self.assigned_ports(&container_id).await.unwrap_or_default()
+        };
+
+        Ok(CreateContainerResult {
+            container_id,
+            ports,
+        })
+    }
+
+    // Cria o container a partir da requisição, sem iniciá-lo
+    async fn create_container_stopped(&self, request: &CreateContainerRequest) -> Result<String> {
         use bollard::models::{
             HostConfig, Mount, MountTypeEnum, PortBinding, RestartPolicy, RestartPolicyNameEnum,
         };
@@ -1258,10 +2034,17 @@ impl DockerManager {
             ));
         }
 
+        for port_map in &request.ports {
+            validate_port_mapping(port_map)?;
+        }
+
+        // Verifica as portas antes de criar, em vez de falhar só no daemon
+        self.ensure_ports_available(&request.ports).await?;
+
         // Verifica se a imagem existe localmente, se não, tenta fazer pull
         if !self.image_exists(&request.image).await? {
             // Aqui podemos adicionar callback de progresso no futuro
-            self.pull_image(&request.image).await?;
+            self.pull_image(&request.image, None).await?;
         }
 
         // Configura mapeamento de portas
@@ -1269,15 +2052,26 @@ impl DockerManager {
         let mut exposed_ports: HashMap<String, HashMap<(), ()>> = HashMap::new();
 
         for port_map in &request.ports {
-            let container_port_key = format!("{}/{}", port_map.container_port, port_map.protocol);
-            port_bindings.insert(
-                container_port_key.clone(),
-                Some(vec![PortBinding {
-                    host_ip: Some("0.0.0.0".to_string()),
-                    host_port: Some(port_map.host_port.to_string()),
-                }]),
-            );
-            exposed_ports.insert(container_port_key, HashMap::new());
+            let host_ip = port_map
+                .host_ip
+                .clone()
+                .filter(|ip| !ip.trim().is_empty())
+                .unwrap_or_else(|| "0.0.0.0".to_string());
+
+            for (index, container_port) in port_map.container_ports().into_iter().enumerate() {
+                let container_port_key =
+                    format!("{}/{}", container_port, port_map.protocol.to_lowercase());
+                // A mesma porta pode ser publicada em mais de um IP (ex.: 127.0.0.1 e ::1)
+                port_bindings
+                    .entry(container_port_key.clone())
+                    .or_insert_with(|| Some(Vec::new()))
+                    .get_or_insert_with(Vec::new)
+                    .push(PortBinding {
+                        host_ip: Some(host_ip.clone()),
+                        host_port: Some(port_map.host_port_binding(index as u16)),
+                    });
+                exposed_ports.insert(container_port_key, HashMap::new());
+            }
         }
 
         // Configura volumes/mounts
@@ -1286,7 +2080,12 @@ impl DockerManager {
             mounts.push(Mount {
                 target: Some(volume_map.container_path.clone()),
                 source: Some(volume_map.host_path.clone()),
-                typ: Some(MountTypeEnum::BIND),
+                // Origem sem caminho é um volume nomeado (ex.: "dados:/var/lib/dados")
+                typ: Some(if is_named_volume(&volume_map.host_path) {
+                    MountTypeEnum::VOLUME
+                } else {
+                    MountTypeEnum::BIND
+                }),
                 read_only: Some(volume_map.read_only),
                 ..Default::default()
             });
@@ -1299,25 +2098,21 @@ impl DockerManager {
             .map(|var| format!("{}={}", var.key, var.value))
             .collect();
 
+        // Container com --rm não pode ter política de restart
+        let auto_remove = request.auto_remove.unwrap_or(false);
+        if auto_remove && !matches!(request.restart_policy.as_str(), "" | "no") {
+            return Err(anyhow::anyhow!(
+                "Remoção automática (--rm) não pode ser usada com política de restart"
+            ));
+        }
+
         // Configura política de restart
-        let restart_policy = match request.restart_policy.as_str() {
-            "always" => Some(RestartPolicy {
-                name: Some(RestartPolicyNameEnum::ALWAYS),
-                maximum_retry_count: None,
-            }),
-            "unless-stopped" => Some(RestartPolicy {
-                name: Some(RestartPolicyNameEnum::UNLESS_STOPPED),
-                maximum_retry_count: None,
-            }),
-            "on-failure" => Some(RestartPolicy {
-                name: Some(RestartPolicyNameEnum::ON_FAILURE),
-                maximum_retry_count: Some(3),
-            }),
-            _ => Some(RestartPolicy {
+        let restart_policy = Some(
+            restart_policy_from_name(&request.restart_policy, None).unwrap_or(RestartPolicy {
                 name: Some(RestartPolicyNameEnum::EMPTY),
                 maximum_retry_count: None,
             }),
-        };
+        );
 
         // Configura comando se especificado
         let cmd = request.command.as_ref().map(|c| {
@@ -1336,13 +2131,19 @@ impl DockerManager {
                 port_bindings: Some(port_bindings),
                 mounts: Some(mounts),
                 restart_policy,
+                network_mode: request
+                    .network
+                    .clone()
+                    .filter(|network| !network.trim().is_empty()),
+                auto_remove: Some(auto_remove),
                 ..Default::default()
             }),
             ..Default::default()
         };
 
+        // Sem nome, o Docker gera um automaticamente
         let options = CreateContainerOptions {
-            name: Some(request.name.clone()),
+            name: Some(request.name.clone()).filter(|name| !name.is_empty()),
             ..Default::default()
         };
 
@@ -1353,12 +2154,185 @@ impl DockerManager {
             .await
             .context("Falha ao criar container")?;
 
-        // Inicia o container automaticamente
-        self.start_container(&response.id)
+        Ok(response.id)
+    }
+
+    // Executa um container até o fim (migrações, seeds): cria, inicia, acompanha os logs
+    // e espera a saída, removendo o container depois se pedido (semântica do --rm)
+    pub async fn run_task(
+        &self,
+        request: RunTaskRequest,
+        on_log: impl Fn(TaskLogLine),
+    ) -> Result<RunTaskResult> {
+        use bollard::container::LogOutput;
+        use bollard::query_parameters::{
+            LogsOptions, RemoveContainerOptions, WaitContainerOptions,
+        };
+        use futures_util::StreamExt;
+        use std::collections::VecDeque;
+
+        let tail_lines = request.tail_lines.unwrap_or(TASK_DEFAULT_TAIL_LINES);
+        let remove = request.remove || request.container.auto_remove.unwrap_or(false);
+
+        // A remoção é feita aqui, depois da espera; o --rm do daemon apagaria o container antes
+        let mut container = request.container;
+        container.auto_remove = Some(false);
+
+        let container_id = self.create_container_stopped(&container).await?;
+        let started_at = std::time::Instant::now();
+
+        let remove_task_container = |container_id: String| async move {
+            self.docker
+                .remove_container(
+                    &container_id,
+                    Some(RemoveContainerOptions {
+                        force: true,
+                        v: true,
+                        ..Default::default()
+                    }),
+                )
+                .await
+                .is_ok()
+        };
+
+        if let Err(e) = self.start_container(&container_id).await {
+            if remove {
+                remove_task_container(container_id.clone()).await;
+            }
+            return Err(e.context("Container criado mas falha ao iniciar"));
+        }
+
+        // Logs completos desde o início, acompanhando até o container parar
+        let logs = async {
+            let mut tail: VecDeque<String> = VecDeque::with_capacity(tail_lines);
+            let mut stream = self.docker.logs(
+                &container_id,
+                Some(LogsOptions {
+                    follow: true,
+                    stdout: true,
+                    stderr: true,
+                    ..Default::default()
+                }),
+            );
+            let mut pending = [String::new(), String::new()];
+
+            while let Some(Ok(output)) = stream.next().await {
+                let (index, stream_name) = match output {
+                    LogOutput::StdErr { .. } => (1, "stderr"),
+                    _ => (0, "stdout"),
+                };
+                pending[index].push_str(&output.to_string());
+
+                // Emite apenas linhas completas; o restante aguarda o próximo bloco
+                while let Some(position) = pending[index].find('\n') {
+                    let line: String = pending[index].drain(..=position).collect();
+                    let line = line.trim_end_matches(['\r', '\n']).to_string();
+                    push_task_line(
+                        &mut tail,
+                        tail_lines,
+                        &container_id,
+                        &line,
+                        stream_name,
+                        &on_log,
+                    );
+                }
+            }
+
+            for (index, rest) in pending.iter().enumerate() {
+                if !rest.is_empty() {
+                    let stream_name = if index == 1 { "stderr" } else { "stdout" };
+                    push_task_line(
+                        &mut tail,
+                        tail_lines,
+                        &container_id,
+                        rest,
+                        stream_name,
+                        &on_log,
+                    );
+                }
+            }
+
+            tail.into_iter().collect::<Vec<String>>()
+        };
+
+        // O bollard devolve erro quando o código de saída é diferente de zero
+        let wait = async {
+            let mut stream = self
+                .docker
+                .wait_container(&container_id, None::<WaitContainerOptions>);
+            match stream.next().await {
+                Some(Ok(response)) => Ok(response.status_code),
+                Some(Err(bollard::errors::Error::DockerContainerWaitError { code, .. })) => {
+                    Ok(code)
+                }
+                Some(Err(e)) => Err(anyhow::anyhow!(
+                    "Falha ao aguardar container {}: {}",
+                    container_id,
+                    e
+                )),
+                None => Err(anyhow::anyhow!(
+                    "Container {} parou sem informar o código de saída",
+                    container_id
+                )),
+            }
+        };
+
+        let (log_tail, exit_code) = tokio::join!(logs, wait);
+        let duration_ms = started_at.elapsed().as_millis() as u64;
+
+        let removed = if remove {
+            remove_task_container(container_id.clone()).await
+        } else {
+            false
+        };
+        let exit_code = exit_code?;
+
+        Ok(RunTaskResult {
+            container_id,
+            exit_code,
+            success: exit_code == 0,
+            duration_ms,
+            log_tail,
+            removed,
+        })
+    }
+
+    // Portas publicadas de fato por um container em execução
+    pub async fn assigned_ports(&self, container_id: &str) -> Result<Vec<AssignedPort>> {
+        use bollard::query_parameters::InspectContainerOptions;
+
+        let inspect = self
+            .docker
+            .inspect_container(container_id, None::<InspectContainerOptions>)
             .await
-            .context("Container criado mas falha ao iniciar")?;
+            .context(format!("Falha ao inspecionar container: {}", container_id))?;
+
+        let mut assigned = Vec::new();
+        let ports = inspect
+            .network_settings
+            .and_then(|settings| settings.ports)
+            .unwrap_or_default();
+
+        for (key, bindings) in ports {
+            let (port, protocol) = key.split_once('/').unwrap_or((key.as_str(), "tcp"));
+            let Ok(container_port) = port.parse::<u16>() else {
+                continue;
+            };
 
-        Ok(response.id)
+            for binding in bindings.unwrap_or_default() {
+                if let Some(host_port) = binding.host_port.and_then(|p| p.parse::<u16>().ok()) {
+                    assigned.push(AssignedPort {
+                        container_port,
+                        protocol: protocol.to_string(),
+                        host_ip: binding.host_ip.unwrap_or_default(),
+                        host_port,
+                    });
+                }
+            }
+        }
+
+        assigned.sort_by_key(|port| (port.container_port, port.host_port));
+        Ok(assigned)
     }
 
     // Verifica se um container com o nome existe
@@ -1381,7 +2355,11 @@ impl DockerManager {
     }
 
     // Faz pull de uma imagem
-    pub async fn pull_image(&self, image_name: &str) -> Result<()> {
+    pub async fn pull_image(
+        &self,
+        image_name: &str,
+        credentials: Option<RegistryCredentials>,
+    ) -> Result<()> {
         use bollard::query_parameters::CreateImageOptions;
         use futures_util::StreamExt;
 
@@ -1390,7 +2368,20 @@ impl DockerManager {
             ..Default::default()
         };
 
-        let mut stream = self.docker.create_image(Some(options), None, None);
+        // Sem credenciais explícitas usa o login salvo; sem login, envia um auth vazio
+        let credentials = match credentials {
+            Some(credentials) => credentials.to_docker_credentials(),
+            None => {
+                let image = image_name.to_string();
+                with_credential_store(move |store| store.credentials_for_image(&image))
+                    .await
+                    .context("Falha ao obter as credenciais do registry")?
+                    .unwrap_or_default()
+            }
+        };
+        let mut stream = self
+            .docker
+            .create_image(Some(options), None, Some(credentials));
 
         while let Some(result) = stream.next().await {
             match result {
@@ -1409,4 +2400,2934 @@ impl DockerManager {
 
         Ok(())
     }
+
+    // Inspeciona uma imagem com tags, digests, configuração e histórico de camadas
+    pub async fn inspect_image(&self, image: &str, reveal_secrets: bool) -> Result<ImageDetails> {
+        let inspect = self
+            .docker
+            .inspect_image(image)
+            .await
+            .context(format!("Falha ao inspecionar imagem: {}", image))?;
+        let history = self
+            .docker
+            .image_history(image)
+            .await
+            .context(format!("Falha ao obter histórico da imagem: {}", image))?;
+
+        let config = inspect.config.unwrap_or_default();
+        let size = inspect.size.unwrap_or_default();
+
+        let mut env: Vec<EnvVar> = config
+            .env
+            .unwrap_or_default()
+            .into_iter()
+            .map(|var| match var.split_once('=') {
+                Some((key, value)) => EnvVar {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                },
+                None => EnvVar {
+                    key: var,
+                    value: String::new(),
+                },
+            })
+            .collect();
+        if !reveal_secrets {
+            mask_env_vars(&mut env);
+        }
+
+        let mut exposed_ports: Vec<String> = config
+            .exposed_ports
+            .unwrap_or_default()
+            .into_keys()
+            .collect();
+        exposed_ports.sort();
+        let mut volumes: Vec<String> = config.volumes.unwrap_or_default().into_keys().collect();
+        volumes.sort();
+
+        // O histórico vem da camada mais recente para a mais antiga; ENV/ARG sensíveis
+        // aparecem nas linhas e são mascarados como o ambiente
+        let layers = history
+            .into_iter()
+            .map(|mut item| {
+                if !reveal_secrets {
+                    item.created_by = mask_secret_assignments(&item.created_by);
+                }
+                item
+            })
+            .map(|item| ImageLayer {
+                instruction: history_instruction(&item.created_by),
+                size_percent: if size > 0 {
+                    (item.size as f64 / size as f64) * 100.0
+                } else {
+                    0.0
+                },
+                empty_layer: item.size == 0,
+                id: item.id,
+                created: item.created,
+                created_by: item.created_by,
+                size: item.size,
+                tags: item.tags,
+                comment: item.comment,
+            })
+            .collect();
+
+        Ok(ImageDetails {
+            id: inspect.id.unwrap_or_default(),
+            repo_tags: inspect.repo_tags.unwrap_or_default(),
+            repo_digests: inspect.repo_digests.unwrap_or_default(),
+            parent: inspect.parent.unwrap_or_default(),
+            created: inspect.created.map(|d| d.to_string()).unwrap_or_default(),
+            author: inspect.author.unwrap_or_default(),
+            architecture: inspect.architecture.unwrap_or_default(),
+            os: inspect.os.unwrap_or_default(),
+            variant: inspect.variant.filter(|variant| !variant.is_empty()),
+            size,
+            labels: config.labels.unwrap_or_default(),
+            env,
+            entrypoint: config.entrypoint.unwrap_or_default(),
+            cmd: config.cmd.unwrap_or_default(),
+            working_dir: config.working_dir.unwrap_or_default(),
+            user: config.user.unwrap_or_default(),
+            exposed_ports,
+            volumes,
+            layers,
+        })
+    }
+
+    // Cria uma nova tag (ex.: "localhost:5000/app:1.0") para uma imagem existente
+    pub async fn tag_image(&self, source: &str, target: &str) -> Result<()> {
+        use bollard::query_parameters::TagImageOptions;
+
+        let (repo, tag) = split_image_reference(target);
+        if repo.is_empty() {
+            return Err(anyhow::anyhow!("Tag de destino inválida: {}", target));
+        }
+
+        self.docker
+            .tag_image(
+                source,
+                Some(TagImageOptions {
+                    repo: Some(repo),
+                    tag: Some(tag.unwrap_or_else(|| "latest".to_string())),
+                }),
+            )
+            .await
+            .context(format!("Falha ao criar tag '{}' para '{}'", target, source))?;
+
+        Ok(())
+    }
+
+    // Remove apenas uma tag, mantendo a imagem enquanto houver outras tags
+    pub async fn untag_image(&self, reference: &str) -> Result<()> {
+        use bollard::query_parameters::RemoveImageOptions;
+
+        let inspect = self
+            .docker
+            .inspect_image(reference)
+            .await
+            .context(format!("Falha ao inspecionar imagem: {}", reference))?;
+        let tags = inspect.repo_tags.unwrap_or_default();
+
+        let (repo, tag) = split_image_reference(reference);
+        let full_reference = format!("{}:{}", repo, tag.unwrap_or_else(|| "latest".to_string()));
+        if !tags.contains(&full_reference) {
+            return Err(anyhow::anyhow!(
+                "'{}' não é uma tag desta imagem",
+                reference
+            ));
+        }
+        if tags.len() == 1 {
+            return Err(anyhow::anyhow!(
+                "'{}' é a única tag da imagem; remova a imagem em vez de remover a tag",
+                reference
+            ));
+        }
+
+        self.docker
+            .remove_image(
+                &full_reference,
+                Some(RemoveImageOptions {
+                    force: false,
+                    noprune: true,
+                }),
+                None,
+            )
+            .await
+            .context(format!("Falha ao remover tag: {}", reference))?;
+
+        Ok(())
+    }
+
+    // Envia uma imagem ao registry, com progresso e credenciais opcionais
+    pub async fn push_image(
+        &self,
+        reference: &str,
+        credentials: Option<RegistryCredentials>,
+        on_progress: impl Fn(ImagePushProgress),
+    ) -> Result<()> {
+        use bollard::query_parameters::PushImageOptions;
+        use futures_util::StreamExt;
+
+        let (repo, tag) = split_image_reference(reference);
+        let tag = tag.unwrap_or_else(|| "latest".to_string());
+        let image = format!("{}:{}", repo, tag);
+
+        // Sem credenciais explícitas usa o login salvo; sem login, envia um auth vazio, pois
+        // o daemon recusa push sem o header X-Registry-Auth (mesmo para registry:2 aberto)
+        let credentials = match credentials {
+            Some(credentials) => credentials.to_docker_credentials(),
+            None => {
+                let image = repo.clone();
+                with_credential_store(move |store| store.credentials_for_image(&image))
+                    .await
+                    .context("Falha ao obter as credenciais do registry")?
+                    .unwrap_or_default()
+            }
+        };
+        let mut stream = self.docker.push_image(
+            &repo,
+            Some(PushImageOptions {
+                tag: Some(tag),
+                ..Default::default()
+            }),
+            Some(credentials),
+        );
+
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok(info) => {
+                    if let Some(error) = info.error_detail.and_then(|detail| detail.message) {
+                        return Err(push_error(&image, &error));
+                    }
+                    let detail = info.progress_detail.unwrap_or_default();
+                    on_progress(ImagePushProgress {
+                        image: image.clone(),
+                        status: info.status.unwrap_or_default(),
+                        progress: info.progress,
+                        current: detail.current,
+                        total: detail.total,
+                    });
+                }
+                Err(e) => return Err(push_error(&image, &e.to_string())),
+            }
+        }
+
+        Ok(())
+    }
+
+    // Compara o digest de cada tag local com o manifesto atual no registry
+    pub async fn check_image_updates(&self) -> Result<ImageUpdateReport> {
+        use bollard::query_parameters::InspectContainerOptions;
+
+        let images = self
+            .docker
+            .list_images(Some(ListImagesOptions {
+                all: false,
+                ..Default::default()
+            }))
+            .await
+            .context("Falha ao listar imagens")?;
+
+        let mut statuses = Vec::new();
+
+        for image in images {
+            for tag in image.repo_tags.iter().filter(|tag| *tag != "<none>:<none>") {
+                let (repo, _) = split_image_reference(tag);
+                // repo_digests guarda "repo@sha256:..." de cada pull/push; a mesma imagem pode
+                // ter vários (ex.: índice multi-arch e manifest da plataforma)
+                let local_digests: Vec<String> = image
+                    .repo_digests
+                    .iter()
+                    .filter_map(|digest| {
+                        digest
+                            .split_once('@')
+                            .filter(|(digest_repo, _)| *digest_repo == repo)
+                            .map(|(_, digest)| digest.to_string())
+                    })
+                    .collect();
+
+                let mut status = ImageUpdateStatus {
+                    image: tag.clone(),
+                    image_id: image.id.clone(),
+                    local_digest: local_digests.first().cloned(),
+                    remote_digest: None,
+                    update_available: false,
+                    error: None,
+                };
+
+                if local_digests.is_empty() {
+                    status.error = Some("Imagem sem digest do registry (build local?)".to_string());
+                    statuses.push(status);
+                    continue;
+                }
+
+                let image_tag = tag.clone();
+                let credentials = match with_credential_store(move |store| {
+                    store.credentials_for_image(&image_tag)
+                })
+                .await
+                {
+                    Ok(credentials) => credentials,
+                    Err(e) => {
+                        status.error = Some(format!("Falha ao obter credenciais: {:#}", e));
+                        statuses.push(status);
+                        continue;
+                    }
+                };
+                match self.docker.inspect_registry_image(tag, credentials).await {
+                    Ok(distribution) => {
+                        let remote_digest = distribution.descriptor.digest;
+                        if let Some(remote) = remote_digest.as_deref() {
+                            status.update_available = !local_digests.iter().any(|d| d == remote);
+                            if !status.update_available {
+                                status.local_digest = Some(remote.to_string());
+                            }
+                        }
+                        status.remote_digest = remote_digest;
+                    }
+                    Err(e) => status.error = Some(format!("Falha ao consultar registry: {}", e)),
+                }
+                statuses.push(status);
+            }
+        }
+
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                ..Default::default()
+            }))
+            .await
+            .context("Falha ao listar containers")?;
+
+        let mut container_statuses = Vec::new();
+        for container in containers {
+            let image_id = container.image_id.unwrap_or_default();
+            let mut image = container.image.unwrap_or_default();
+
+            // Depois que a tag passa para outra imagem, a listagem mostra só o ID da imagem
+            // antiga; o nome usado na criação continua no Config do container
+            if image.starts_with("sha256:") {
+                if let Some(id) = container.id.as_deref() {
+                    if let Some(name) = self
+                        .docker
+                        .inspect_container(id, None::<InspectContainerOptions>)
+                        .await
+                        .ok()
+                        .and_then(|inspect| inspect.config)
+                        .and_then(|config| config.image)
+                    {
+                        image = name;
+                    }
+                }
+            }
+
+            let (repo, tag) = split_image_reference(&image);
+            let reference = format!("{}:{}", repo, tag.as_deref().unwrap_or("latest"));
+
+            // Prefere a tag usada na criação; senão, qualquer tag da mesma imagem
+            let (image, update_available) =
+                match statuses.iter().find(|status| status.image == reference) {
+                    // A tag já aponta para outra imagem local (re-pull): o container está
+                    // rodando uma versão antiga
+                    Some(status) if status.image_id != image_id => (status.image.clone(), true),
+                    Some(status) => (status.image.clone(), status.update_available),
+                    None => match statuses
+                        .iter()
+                        .find(|status| status.image_id == image_id && status.update_available)
+                    {
+                        Some(status) => (status.image.clone(), true),
+                        None => continue,
+                    },
+                };
+
+            container_statuses.push(ContainerUpdateStatus {
+                container_id: container.id.unwrap_or_default(),
+                container_name: container
+                    .names
+                    .and_then(|names| names.into_iter().next())
+                    .map(|name| name.trim_start_matches('/').to_string())
+                    .unwrap_or_default(),
+                image,
+                update_available,
+            });
+        }
+
+        Ok(ImageUpdateReport {
+            images: statuses,
+            containers: container_statuses,
+        })
+    }
+
+    // Salva várias imagens em um único tar, gravando em disco à medida que chega
+    pub async fn save_images(
+        &self,
+        operation_id: &str,
+        images: &[String],
+        destination: &str,
+        cancel: CancellationToken,
+        on_progress: impl Fn(ImageArchiveProgress),
+    ) -> Result<u64> {
+        use futures_util::StreamExt;
+        use tokio::io::AsyncWriteExt;
+
+        if images.is_empty() {
+            return Err(anyhow::anyhow!("Nenhuma imagem selecionada"));
+        }
+
+        // O tamanho das imagens serve como estimativa do tamanho do tar
+        let mut estimated_total = 0u64;
+        for image in images {
+            let inspect = self
+                .docker
+                .inspect_image(image)
+                .await
+                .context(format!("Imagem não encontrada: {}", image))?;
+            estimated_total += inspect.size.unwrap_or_default().max(0) as u64;
+        }
+
+        let progress = |bytes_transferred: u64, done: bool| ImageArchiveProgress {
+            operation_id: operation_id.to_string(),
+            path: destination.to_string(),
+            direction: "save".to_string(),
+            bytes_transferred,
+            total_bytes: Some(estimated_total.max(bytes_transferred)),
+            done,
+        };
+
+        let mut file = tokio::fs::File::create(destination)
+            .await
+            .context(format!("Falha ao criar arquivo: {}", destination))?;
+
+        let names: Vec<&str> = images.iter().map(String::as_str).collect();
+        let mut stream = self.docker.export_images(&names);
+        let mut written = 0u64;
+        let mut last_reported = 0u64;
+
+        let result: Result<()> = loop {
+            let chunk = tokio::select! {
+                _ = cancel.cancelled() => break Err(anyhow::anyhow!("Operação cancelada")),
+                chunk = stream.next() => chunk,
+            };
+            let bytes = match chunk {
+                Some(Ok(bytes)) => bytes,
+                Some(Err(e)) => break Err(anyhow::anyhow!("Falha ao exportar imagens: {}", e)),
+                None => break Ok(()),
+            };
+
+            if let Err(e) = file.write_all(&bytes).await {
+                break Err(anyhow::anyhow!("Falha ao gravar {}: {}", destination, e));
+            }
+            written += bytes.len() as u64;
+            if written - last_reported >= TRANSFER_PROGRESS_STEP {
+                on_progress(progress(written, false));
+                last_reported = written;
+            }
+        };
+
+        let result = match result {
+            Ok(()) => file
+                .flush()
+                .await
+                .context(format!("Falha ao gravar {}", destination)),
+            Err(e) => Err(e),
+        };
+
+        // Não deixa arquivo incompleto para trás
+        if let Err(e) = result {
+            drop(file);
+            let _ = tokio::fs::remove_file(destination).await;
+            return Err(e);
+        }
+
+        on_progress(progress(written, true));
+        Ok(written)
+    }
+
+    // Carrega imagens de um tar (docker load) lendo o arquivo em blocos
+    pub async fn load_images(
+        &self,
+        operation_id: &str,
+        source: &str,
+        cancel: CancellationToken,
+        on_progress: impl Fn(ImageArchiveProgress) + Send + Sync + 'static,
+    ) -> Result<LoadImagesResult> {
+        use bollard::query_parameters::ImportImageOptions;
+        use futures_util::StreamExt;
+        use std::sync::Arc;
+        use tokio_util::io::ReaderStream;
+
+        let total = tokio::fs::metadata(source)
+            .await
+            .context(format!("Arquivo não encontrado: {}", source))?
+            .len();
+        let file = tokio::fs::File::open(source)
+            .await
+            .context(format!("Falha ao abrir arquivo: {}", source))?;
+
+        let on_progress = Arc::new(on_progress);
+        let operation_id_owned = operation_id.to_string();
+        let source_owned = source.to_string();
+        let progress = move |bytes_transferred: u64, done: bool| ImageArchiveProgress {
+            operation_id: operation_id_owned.clone(),
+            path: source_owned.clone(),
+            direction: "load".to_string(),
+            bytes_transferred,
+            total_bytes: Some(total),
+            done,
+        };
+
+        let body = {
+            let on_progress = on_progress.clone();
+            let progress = progress.clone();
+            let mut transferred = 0u64;
+            let mut last_reported = 0u64;
+            ReaderStream::with_capacity(file, 256 * 1024).inspect(move |chunk| {
+                if let Ok(bytes) = chunk {
+                    transferred += bytes.len() as u64;
+                    if transferred - last_reported >= TRANSFER_PROGRESS_STEP {
+                        on_progress(progress(transferred, false));
+                        last_reported = transferred;
+                    }
+                }
+            })
+        };
+
+        let mut stream = self.docker.import_image(
+            ImportImageOptions {
+                quiet: false,
+                ..Default::default()
+            },
+            bollard::body_try_stream(body),
+            None,
+        );
+
+        // O daemon responde "Loaded image: repo:tag" ou "Loaded image ID: sha256:..."
+        let mut loaded = Vec::new();
+        loop {
+            let item = tokio::select! {
+                _ = cancel.cancelled() => return Err(anyhow::anyhow!("Operação cancelada")),
+                item = stream.next() => item,
+            };
+            let info = match item {
+                Some(Ok(info)) => info,
+                Some(Err(e)) => return Err(anyhow::anyhow!("Falha ao carregar imagens: {}", e)),
+                None => break,
+            };
+
+            if let Some(error) = info
+                .error_detail
+                .and_then(|detail| detail.message)
+                .or(info.error)
+            {
+                return Err(anyhow::anyhow!("Falha ao carregar imagens: {}", error));
+            }
+
+            let text = info.stream.or(info.status).unwrap_or_default();
+            for line in text.lines() {
+                let loaded_image = line
+                    .strip_prefix("Loaded image ID:")
+                    .or_else(|| line.strip_prefix("Loaded image:"));
+                if let Some(image) = loaded_image {
+                    loaded.push(image.trim().to_string());
+                }
+            }
+        }
+
+        on_progress(progress(total, true));
+        Ok(LoadImagesResult {
+            loaded,
+            bytes_read: total,
+        })
+    }
+
+    // Constrói uma imagem enviando o contexto (respeitando o .dockerignore) ao daemon
+    pub async fn build_image(
+        &self,
+        request: BuildImageRequest,
+        on_log: impl Fn(BuildLogLine),
+    ) -> Result<BuildImageResult> {
+        use bollard::query_parameters::BuildImageOptions;
+        use futures_util::StreamExt;
+        use tokio_util::io::ReaderStream;
+
+        let dockerfile = request
+            .dockerfile
+            .clone()
+            .filter(|dockerfile| !dockerfile.trim().is_empty())
+            .unwrap_or_else(|| "Dockerfile".to_string());
+        let tag = request.tag.clone().filter(|tag| !tag.trim().is_empty());
+
+        // O contexto vai para um tar temporário em disco, não para a memória
+        let archive_path = temp_archive_path();
+        let context_dir = PathBuf::from(&request.context_dir);
+        let archive_for_build = archive_path.clone();
+        let dockerfile_for_build = dockerfile.clone();
+        let built = tokio::task::spawn_blocking(move || {
+            create_build_context(&context_dir, &dockerfile_for_build, &archive_for_build)
+        })
+        .await
+        .context("Falha ao preparar contexto de build")?;
+        let context_files = match built {
+            Ok(count) => count,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&archive_path).await;
+                return Err(e);
+            }
+        };
+
+        let file = tokio::fs::File::open(&archive_path)
+            .await
+            .context("Falha ao abrir contexto de build")?;
+
+        let options = BuildImageOptions {
+            dockerfile: dockerfile.replace('\\', "/"),
+            t: tag.clone(),
+            buildargs: request.build_args.clone().filter(|args| !args.is_empty()),
+            target: request.target.clone().unwrap_or_default(),
+            nocache: request.no_cache,
+            pull: request.pull.then(|| "true".to_string()),
+            rm: true,
+            ..Default::default()
+        };
+
+        // Credenciais de todos os registries, para imagens base privadas no FROM
+        let credentials = with_credential_store(|store| store.all_credentials())
+            .await
+            .context("Falha ao obter as credenciais dos registries")?;
+        let mut stream = self.docker.build_image(
+            options,
+            Some(credentials),
+            Some(bollard::body_try_stream(ReaderStream::with_capacity(
+                file,
+                256 * 1024,
+            ))),
+        );
+
+        let mut image_id: Option<String> = None;
+        let mut current_step: Option<String> = None;
+        let mut failure: Option<String> = None;
+
+        while let Some(result) = stream.next().await {
+            let info = match result {
+                Ok(info) => info,
+                Err(e) => {
+                    failure = Some(e.to_string());
+                    break;
+                }
+            };
+
+            if let Some(aux_id) = info.aux.and_then(|aux| aux.id) {
+                image_id = Some(aux_id);
+            }
+
+            if let Some(text) = info.stream.or(info.status) {
+                for line in text.lines().filter(|line| !line.trim().is_empty()) {
+                    // Builder clássico: "Step 3/7 : RUN npm ci"
+                    if line.starts_with("Step ") {
+                        current_step = Some(line.trim().to_string());
+                    }
+                    if let Some(id) = line.strip_prefix("Successfully built ") {
+                        image_id.get_or_insert_with(|| id.trim().to_string());
+                    }
+                    on_log(BuildLogLine {
+                        line: line.to_string(),
+                        step: current_step.clone(),
+                    });
+                }
+            }
+
+            if let Some(error) = info
+                .error_detail
+                .and_then(|detail| detail.message)
+                .or(info.error)
+            {
+                failure = Some(error);
+                break;
+            }
+        }
+
+        let _ = tokio::fs::remove_file(&archive_path).await;
+
+        if let Some(error) = failure {
+            return Err(match current_step {
+                Some(step) => anyhow::anyhow!("Build falhou em \"{}\": {}", step, error.trim()),
+                None => anyhow::anyhow!("Build falhou: {}", error.trim()),
+            });
+        }
+
+        // Sem id no stream, usa a tag para descobrir a imagem gerada
+        let image_id = match (image_id, &tag) {
+            (Some(id), _) => id,
+            (None, Some(tag)) => self
+                .docker
+                .inspect_image(tag)
+                .await
+                .context(format!("Falha ao inspecionar imagem: {}", tag))?
+                .id
+                .unwrap_or_default(),
+            (None, None) => {
+                return Err(anyhow::anyhow!(
+                    "Build concluído sem identificador de imagem"
+                ))
+            }
+        };
+
+        Ok(BuildImageResult {
+            image_id,
+            tag,
+            context_files,
+        })
+    }
+
+    // Executa um comando dentro do container e retorna (exit code, stdout, stderr)
+    pub async fn exec_capture(
+        &self,
+        container_id: &str,
+        cmd: Vec<String>,
+    ) -> Result<(i64, String, String)> {
+        use bollard::container::LogOutput;
+        use bollard::exec::StartExecResults;
+        use bollard::models::ExecConfig;
+        use futures_util::StreamExt;
+
+        let exec = self
+            .docker
+            .create_exec(
+                container_id,
+                ExecConfig {
+                    cmd: Some(cmd),
+                    attach_stdout: Some(true),
+                    attach_stderr: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await
+            .context(format!(
+                "Falha ao criar exec no container: {}",
+                container_id
+            ))?;
+
+        let mut stdout = String::new();
+        let mut stderr = String::new();
+
+        if let StartExecResults::Attached { mut output, .. } = self
+            .docker
+            .start_exec(&exec.id, None)
+            .await
+            .context("Falha ao iniciar exec")?
+        {
+            while let Some(chunk) = output.next().await {
+                match chunk {
+                    Ok(LogOutput::StdOut { message }) => {
+                        stdout.push_str(&String::from_utf8_lossy(&message))
+                    }
+                    Ok(LogOutput::StdErr { message }) => {
+                        stderr.push_str(&String::from_utf8_lossy(&message))
+                    }
+                    Ok(_) => {}
+                    Err(_) => break,
+                }
+            }
+        }
+
+        let inspect = self
+            .docker
+            .inspect_exec(&exec.id)
+            .await
+            .context("Falha ao inspecionar exec")?;
+
+        Ok((inspect.exit_code.unwrap_or(-1), stdout, stderr))
+    }
+
+    // Verifica se o container está em execução
+    async fn is_container_running(&self, container_id: &str) -> Result<bool> {
+        use bollard::query_parameters::InspectContainerOptions;
+
+        let inspect = self
+            .docker
+            .inspect_container(container_id, None::<InspectContainerOptions>)
+            .await
+            .context(format!("Falha ao inspecionar container: {}", container_id))?;
+
+        Ok(inspect
+            .state
+            .and_then(|state| state.running)
+            .unwrap_or(false))
+    }
+
+    // Lista o conteúdo de um diretório dentro do container
+    pub async fn list_container_files(
+        &self,
+        container_id: &str,
+        path: &str,
+    ) -> Result<Vec<ContainerFileEntry>> {
+        let dir = format!("/{}", path.trim_matches('/'));
+
+        // Com o container rodando, usa stat via exec para não baixar o diretório inteiro
+        if self.is_container_running(container_id).await? {
+            let script = r#"[ -d "$1/" ] || exit 2
+for f in "$1"/* "$1"/.[!.]* "$1"/..?*; do
+  if [ -e "$f" ] || [ -L "$f" ]; then stat -c '%F|%s|%Y|%f|%n' -- "$f"; fi
+done
+exit 0"#;
+            let cmd = vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                script.to_string(),
+                "sh".to_string(),
+                dir.trim_end_matches('/').to_string(),
+            ];
+
+            if let Ok((0, stdout, _)) = self.exec_capture(container_id, cmd).await {
+                let mut entries: Vec<ContainerFileEntry> =
+                    stdout.lines().filter_map(parse_stat_line).collect();
+                entries.sort_by(|a, b| a.name.cmp(&b.name));
+                return Ok(entries);
+            }
+        }
+
+        // Container parado ou sem shell: lê a listagem do arquivo tar
+        self.list_container_files_from_archive(container_id, &dir)
+            .await
+    }
+
+    // Lista um diretório do container a partir da API de arquivos (funciona parado)
+    async fn list_container_files_from_archive(
+        &self,
+        container_id: &str,
+        dir: &str,
+    ) -> Result<Vec<ContainerFileEntry>> {
+        let base_dir = dir.trim_end_matches('/').to_string();
+        let mut entries = self
+            .read_container_archive(container_id, dir, move |archive| {
+                list_archive_children(archive, &base_dir)
+            })
+            .await?;
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entries)
+    }
+
+    // Lê o tar de um caminho do container direto do stream da API, sem gravar em disco.
+    // `read` roda fora do runtime; ao retornar, o restante do stream é descartado
+    async fn read_container_archive<T, F>(
+        &self,
+        container_id: &str,
+        container_path: &str,
+        read: F,
+    ) -> Result<T>
+    where
+        F: FnOnce(&mut dyn std::io::Read) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        use bollard::query_parameters::DownloadFromContainerOptions;
+        use futures_util::StreamExt;
+        use tokio_util::io::{StreamReader, SyncIoBridge};
+
+        let stream = self
+            .docker
+            .download_from_container(
+                container_id,
+                Some(DownloadFromContainerOptions {
+                    path: container_path.to_string(),
+                }),
+            )
+            .map(|chunk| chunk.map_err(std::io::Error::other));
+        let mut reader = SyncIoBridge::new(StreamReader::new(Box::pin(stream)));
+
+        let path = container_path.to_string();
+        tokio::task::spawn_blocking(move || {
+            read(&mut reader)
+                .map_err(|e| anyhow::anyhow!("Falha ao ler '{}' do container: {:#}", path, e))
+        })
+        .await
+        .context("Falha ao ler arquivos do container")?
+    }
+
+    // Grava o tar de um caminho do container em um arquivo local, sem carregar em memória
+    async fn download_archive_to_file(
+        &self,
+        container_id: &str,
+        container_path: &str,
+        dest: &Path,
+        on_bytes: impl Fn(u64),
+    ) -> Result<u64> {
+        use bollard::query_parameters::DownloadFromContainerOptions;
+        use futures_util::StreamExt;
+        use tokio::io::AsyncWriteExt;
+
+        let mut file = tokio::fs::File::create(dest)
+            .await
+            .context("Falha ao criar arquivo temporário")?;
+
+        let stream = self.docker.download_from_container(
+            container_id,
+            Some(DownloadFromContainerOptions {
+                path: container_path.to_string(),
+            }),
+        );
+        futures_util::pin_mut!(stream);
+
+        let mut transferred = 0u64;
+        let mut last_reported = 0u64;
+        while let Some(chunk) = stream.next().await {
+            let chunk =
+                chunk.context(format!("Falha ao baixar '{}' do container", container_path))?;
+            file.write_all(&chunk)
+                .await
+                .context("Falha ao gravar arquivo temporário")?;
+
+            transferred += chunk.len() as u64;
+            if transferred - last_reported >= TRANSFER_PROGRESS_STEP {
+                on_bytes(transferred);
+                last_reported = transferred;
+            }
+        }
+
+        file.flush()
+            .await
+            .context("Falha ao gravar arquivo temporário")?;
+
+        Ok(transferred)
+    }
+
+    // Baixa um arquivo ou diretório do container para um caminho do host
+    pub async fn download_from_container(
+        &self,
+        container_id: &str,
+        container_path: &str,
+        host_path: &str,
+        on_progress: impl Fn(TransferProgress),
+    ) -> Result<u64> {
+        let progress = |bytes_transferred: u64, done: bool| TransferProgress {
+            container_id: container_id.to_string(),
+            path: container_path.to_string(),
+            direction: "download".to_string(),
+            bytes_transferred,
+            total_bytes: None,
+            done,
+        };
+
+        let archive_path = temp_archive_path();
+        let downloaded = self
+            .download_archive_to_file(container_id, container_path, &archive_path, |bytes| {
+                on_progress(progress(bytes, false))
+            })
+            .await;
+        let transferred = match downloaded {
+            Ok(transferred) => transferred,
+            Err(e) => {
+                let _ = tokio::fs::remove_file(&archive_path).await;
+                return Err(e);
+            }
+        };
+
+        let archive_for_extract = archive_path.clone();
+        let target = PathBuf::from(host_path);
+        let extracted =
+            tokio::task::spawn_blocking(move || extract_archive(&archive_for_extract, &target))
+                .await
+                .context("Falha ao extrair arquivos baixados")?;
+
+        let _ = tokio::fs::remove_file(&archive_path).await;
+        extracted?;
+
+        on_progress(progress(transferred, true));
+        Ok(transferred)
+    }
+
+    // Envia arquivos ou diretórios do host para um diretório do container
+    pub async fn upload_to_container(
+        &self,
+        container_id: &str,
+        host_paths: &[String],
+        container_path: &str,
+        on_progress: impl Fn(TransferProgress) + Send + Sync + 'static,
+    ) -> Result<u64> {
+        use bollard::query_parameters::UploadToContainerOptions;
+        use futures_util::StreamExt;
+        use std::sync::Arc;
+        use tokio_util::io::ReaderStream;
+
+        if host_paths.is_empty() {
+            return Err(anyhow::anyhow!("Nenhum arquivo selecionado para envio"));
+        }
+
+        // Monta o tar em disco para suportar arquivos grandes
+        let archive_path = temp_archive_path();
+        let sources: Vec<PathBuf> = host_paths.iter().map(PathBuf::from).collect();
+        let archive_for_build = archive_path.clone();
+        let built =
+            tokio::task::spawn_blocking(move || build_archive(&sources, &archive_for_build))
+                .await
+                .context("Falha ao preparar arquivos para envio")?;
+        if let Err(e) = built {
+            let _ = tokio::fs::remove_file(&archive_path).await;
+            return Err(e);
+        }
+
+        let total = tokio::fs::metadata(&archive_path)
+            .await
+            .context("Falha ao ler arquivo temporário")?
+            .len();
+        let file = tokio::fs::File::open(&archive_path)
+            .await
+            .context("Falha ao abrir arquivo temporário")?;
+
+        let on_progress = Arc::new(on_progress);
+        let container_id_owned = container_id.to_string();
+        let container_path_owned = container_path.to_string();
+        let progress = move |bytes_transferred: u64, done: bool| TransferProgress {
+            container_id: container_id_owned.clone(),
+            path: container_path_owned.clone(),
+            direction: "upload".to_string(),
+            bytes_transferred,
+            total_bytes: Some(total),
+            done,
+        };
+
+        let stream = {
+            let on_progress = on_progress.clone();
+            let progress = progress.clone();
+            let mut transferred = 0u64;
+            let mut last_reported = 0u64;
+            ReaderStream::with_capacity(file, 256 * 1024).inspect(move |chunk| {
+                if let Ok(bytes) = chunk {
+                    transferred += bytes.len() as u64;
+                    if transferred - last_reported >= TRANSFER_PROGRESS_STEP {
+                        on_progress(progress(transferred, false));
+                        last_reported = transferred;
+                    }
+                }
+            })
+        };
+
+        let result = self
+            .docker
+            .upload_to_container(
+                container_id,
+                Some(UploadToContainerOptions {
+                    path: container_path.to_string(),
+                    ..Default::default()
+                }),
+                bollard::body_try_stream(stream),
+            )
+            .await;
+
+        let _ = tokio::fs::remove_file(&archive_path).await;
+        result.context(format!(
+            "Falha ao enviar arquivos para '{}' no container",
+            container_path
+        ))?;
+
+        on_progress(progress(total, true));
+        Ok(total)
+    }
+
+    // Lista os processos de um container usando os argumentos do ps informados
+    pub async fn container_top(
+        &self,
+        container_id: &str,
+        ps_args: Option<String>,
+    ) -> Result<ContainerTop> {
+        use bollard::query_parameters::{InspectContainerOptions, TopOptions};
+
+        let top = self
+            .docker
+            .top_processes(
+                container_id,
+                Some(TopOptions {
+                    ps_args: ps_args
+                        .filter(|args| !args.trim().is_empty())
+                        .unwrap_or_else(|| "aux".to_string()),
+                }),
+            )
+            .await
+            .context(format!(
+                "Falha ao listar processos do container: {}",
+                container_id
+            ))?;
+
+        // PIDs do namespace do container só podem ser lidos do /proc quando o daemon é local
+        let inspect = self
+            .docker
+            .inspect_container(container_id, None::<InspectContainerOptions>)
+            .await
+            .context(format!("Falha ao inspecionar container: {}", container_id))?;
+        let local_proc = container_visible_in_local_proc(
+            inspect.id.as_deref().unwrap_or_default(),
+            inspect
+                .state
+                .and_then(|state| state.pid)
+                .unwrap_or_default(),
+        );
+
+        let titles = top.titles.unwrap_or_default();
+        let column = |names: &[&str]| {
+            titles
+                .iter()
+                .position(|title| names.contains(&title.to_uppercase().as_str()))
+        };
+        let pid_col = column(&["PID"]);
+        let user_col = column(&["USER", "UID", "RUSER"]);
+        let cpu_col = column(&["%CPU", "C", "PCPU"]);
+        let mem_col = column(&["%MEM", "PMEM"]);
+        let cmd_col = column(&["COMMAND", "CMD", "ARGS"]);
+
+        let processes = top
+            .processes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|values| {
+                let value = |col: Option<usize>| {
+                    col.and_then(|i| values.get(i)).cloned().unwrap_or_default()
+                };
+                let pid = value(pid_col);
+
+                ContainerProcess {
+                    container_pid: local_proc.then(|| namespace_pid(&pid)).flatten(),
+                    user: value(user_col),
+                    cpu_percentage: value(cpu_col).parse().ok(),
+                    memory_percentage: value(mem_col).parse().ok(),
+                    command: value(cmd_col),
+                    pid,
+                    values,
+                }
+            })
+            .collect();
+
+        Ok(ContainerTop { titles, processes })
+    }
+
+    // Envia um sinal para um processo do container (PID no namespace do container)
+    pub async fn signal_container_process(
+        &self,
+        container_id: &str,
+        container_pid: u32,
+        signal: &str,
+    ) -> Result<()> {
+        let signal = normalize_signal(signal)?;
+
+        // O processo principal recebe o sinal pela API, como no `docker kill --signal`
+        if container_pid == 1 {
+            return self.kill_container(container_id, &signal).await;
+        }
+
+        let (exit_code, _, stderr) = self
+            .exec_capture(
+                container_id,
+                vec![
+                    "kill".to_string(),
+                    "-s".to_string(),
+                    signal.clone(),
+                    container_pid.to_string(),
+                ],
+            )
+            .await?;
+
+        if exit_code != 0 {
+            return Err(anyhow::anyhow!(
+                "Falha ao enviar {} ao processo {}: {}",
+                signal,
+                container_pid,
+                stderr.trim()
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Lista arquivos adicionados, modificados e removidos em relação à imagem
+    pub async fn container_changes(
+        &self,
+        container_id: &str,
+        with_sizes: bool,
+    ) -> Result<ContainerChanges> {
+        use bollard::models::ChangeType;
+        use futures_util::StreamExt;
+
+        let changes = self
+            .docker
+            .container_changes(container_id)
+            .await
+            .context(format!(
+                "Falha ao obter alterações do container: {}",
+                container_id
+            ))?
+            .unwrap_or_default();
+
+        let mut entries: Vec<(String, &str)> = changes
+            .into_iter()
+            .map(|change| {
+                let kind = match change.kind {
+                    ChangeType::_0 => "modified",
+                    ChangeType::_1 => "added",
+                    ChangeType::_2 => "deleted",
+                };
+                (change.path, kind)
+            })
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut added = Vec::new();
+        let mut modified = Vec::new();
+        let mut deleted = Vec::new();
+        let mut tree = Vec::new();
+
+        for (path, kind) in &entries {
+            match *kind {
+                "added" => added.push(path.clone()),
+                "modified" => modified.push(path.clone()),
+                _ => deleted.push(path.clone()),
+            }
+
+            let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+            if !components.is_empty() {
+                insert_change_node(&mut tree, "", &components, kind, None);
+            }
+        }
+
+        let total_size = if with_sizes {
+            // Tamanho apenas das folhas da árvore; diretórios somam os filhos depois
+            let mut leaves = Vec::new();
+            collect_change_leaves(&tree, &mut leaves);
+
+            let sizes: HashMap<String, u64> = futures_util::stream::iter(leaves)
+                .map(|path| async move {
+                    // Caminho que sumiu ou falhou fica sem tamanho, sem abortar os demais
+                    let size = self.container_path_size(container_id, &path).await.ok();
+                    (path, size)
+                })
+                .buffer_unordered(CHANGE_STAT_CONCURRENCY)
+                .filter_map(|(path, size)| async move { size.map(|size| (path, size)) })
+                .collect()
+                .await;
+
+            set_change_sizes(&mut tree, &sizes);
+            // Um tamanho desconhecido deixa o total (e os diretórios acima dele) sem valor,
+            // em vez de um total parcial
+            tree.iter_mut()
+                .map(sum_change_sizes)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .sum()
+        } else {
+            None
+        };
+
+        Ok(ContainerChanges {
+            added,
+            modified,
+            deleted,
+            tree,
+            total_size,
+        })
+    }
+
+    // Tamanho do conteúdo de um caminho do container: só o primeiro header do tar é lido.
+    // Diretórios não têm conteúdo próprio; o tamanho vem dos filhos
+    async fn container_path_size(&self, container_id: &str, path: &str) -> Result<u64> {
+        self.read_container_archive(container_id, path, |reader| {
+            let mut archive = tar::Archive::new(reader);
+            let entry = archive
+                .entries()?
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("Arquivo tar vazio"))??;
+            let header = entry.header();
+            if header.entry_type().is_file() {
+                Ok(entry.size())
+            } else {
+                Ok(0)
+            }
+        })
+        .await
+    }
+
+    // Gera uma nova imagem a partir do estado atual de um container
+    pub async fn commit_container(&self, request: CommitContainerRequest) -> Result<String> {
+        use bollard::models::ContainerConfig;
+        use bollard::query_parameters::CommitContainerOptions;
+
+        let repo = request.repo.trim();
+        if repo.is_empty() {
+            return Err(anyhow::anyhow!("Informe o repositório da nova imagem"));
+        }
+
+        // Valida as instruções antes de enviar ao daemon
+        let changes: Vec<&str> = request
+            .changes
+            .iter()
+            .map(|change| change.trim())
+            .filter(|change| !change.is_empty())
+            .collect();
+        for change in &changes {
+            let instruction = change
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_uppercase();
+            if !COMMIT_CHANGE_INSTRUCTIONS.contains(&instruction.as_str()) {
+                return Err(anyhow::anyhow!(
+                    "Instrução não suportada no commit: {}",
+                    change
+                ));
+            }
+        }
+
+        let tag = request
+            .tag
+            .as_deref()
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .unwrap_or("latest")
+            .to_string();
+
+        let options = CommitContainerOptions {
+            container: Some(request.container_id.clone()),
+            repo: Some(repo.to_string()),
+            tag: Some(tag.clone()),
+            comment: request.message.clone().filter(|m| !m.trim().is_empty()),
+            author: request.author.clone().filter(|a| !a.trim().is_empty()),
+            pause: request.pause,
+            changes: if changes.is_empty() {
+                None
+            } else {
+                Some(changes.join("\n"))
+            },
+        };
+
+        let commit = self
+            .docker
+            .commit_container(options, ContainerConfig::default())
+            .await
+            .context(format!(
+                "Falha ao gerar imagem do container: {}",
+                request.container_id
+            ))?;
+
+        // A resposta nem sempre traz o id; nesse caso consulta a imagem recém criada
+        if let Some(id) = commit.id.filter(|id| !id.is_empty()) {
+            return Ok(id);
+        }
+
+        let image = self
+            .docker
+            .inspect_image(&format!("{}:{}", repo, tag))
+            .await
+            .context("Imagem criada mas não encontrada")?;
+
+        Ok(image.id.unwrap_or_default())
+    }
+
+    // Atualiza limites de recursos e política de restart sem recriar o container
+    pub async fn update_container(
+        &self,
+        container_id: &str,
+        request: UpdateContainerRequest,
+    ) -> Result<bollard::models::HostConfig> {
+        use bollard::models::ContainerUpdateBody;
+        use bollard::query_parameters::InspectContainerOptions;
+
+        let current = self.get_container_resources(container_id).await?;
+        self.validate_update_request(&request, &current).await?;
+
+        // Só as tentativas mudaram: mantém a política atual
+        let policy_name = match (
+            request.restart_policy.as_deref(),
+            request.restart_max_retries,
+        ) {
+            (Some(name), _) => Some(name),
+            (None, Some(_)) => Some(current.restart_policy.as_str()),
+            (None, None) => None,
+        };
+        let restart_policy = match policy_name {
+            Some(name) => Some(
+                restart_policy_from_name(name, request.restart_max_retries)
+                    .ok_or_else(|| anyhow::anyhow!("Política de restart inválida: {}", name))?,
+            ),
+            None => None,
+        };
+
+        self.docker
+            .update_container(
+                container_id,
+                ContainerUpdateBody {
+                    memory: request.memory,
+                    memory_swap: request.memory_swap,
+                    cpu_shares: request.cpu_shares,
+                    cpu_quota: request.cpu_quota,
+                    cpu_period: request.cpu_period,
+                    cpuset_cpus: request.cpuset_cpus.clone(),
+                    pids_limit: request.pids_limit,
+                    restart_policy,
+                    ..Default::default()
+                },
+            )
+            .await
+            .context(format!("Falha ao atualizar container: {}", container_id))?;
+
+        // Devolve o HostConfig efetivamente aplicado pelo daemon
+        let inspect = self
+            .docker
+            .inspect_container(container_id, None::<InspectContainerOptions>)
+            .await
+            .context(format!("Falha ao inspecionar container: {}", container_id))?;
+        Ok(inspect.host_config.unwrap_or_default())
+    }
+
+    // Lê os limites atuais do HostConfig de um container
+    async fn get_container_resources(&self, container_id: &str) -> Result<ContainerResources> {
+        use bollard::query_parameters::InspectContainerOptions;
+
+        let inspect = self
+            .docker
+            .inspect_container(container_id, None::<InspectContainerOptions>)
+            .await
+            .context(format!("Falha ao inspecionar container: {}", container_id))?;
+
+        let host_config = inspect.host_config.unwrap_or_default();
+        let restart_policy = host_config.restart_policy.unwrap_or_default();
+
+        Ok(ContainerResources {
+            memory: host_config.memory.unwrap_or(0),
+            memory_swap: host_config.memory_swap.unwrap_or(0),
+            cpu_shares: host_config.cpu_shares.unwrap_or(0),
+            cpu_quota: host_config.cpu_quota.unwrap_or(0),
+            cpu_period: host_config.cpu_period.unwrap_or(0),
+            nano_cpus: host_config.nano_cpus.unwrap_or(0),
+            cpuset_cpus: host_config.cpuset_cpus.unwrap_or_default(),
+            pids_limit: host_config.pids_limit.unwrap_or(0),
+            restart_policy: restart_policy
+                .name
+                .map(|name| name.to_string())
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| "no".to_string()),
+            restart_max_retries: restart_policy.maximum_retry_count.unwrap_or(0),
+        })
+    }
+
+    // Valida os novos limites antes de enviá-los ao daemon
+    async fn validate_update_request(
+        &self,
+        request: &UpdateContainerRequest,
+        current: &ContainerResources,
+    ) -> Result<()> {
+        if let Some(memory) = request.memory {
+            if memory != 0 && memory < MIN_CONTAINER_MEMORY {
+                return Err(anyhow::anyhow!("O limite mínimo de memória é 6 MB"));
+            }
+        }
+
+        // Swap precisa ser maior ou igual à memória efetiva (ou -1 para ilimitado)
+        if let Some(memory_swap) = request.memory_swap {
+            let memory = request.memory.unwrap_or(current.memory);
+            if memory_swap != -1 && memory_swap != 0 {
+                if memory == 0 {
+                    return Err(anyhow::anyhow!(
+                        "Defina um limite de memória antes de limitar o swap"
+                    ));
+                }
+                if memory_swap < memory {
+                    return Err(anyhow::anyhow!(
+                        "O limite de memória + swap deve ser maior ou igual ao limite de memória"
+                    ));
+                }
+            }
+        } else if let Some(memory) = request.memory {
+            if current.memory_swap > 0 && memory > current.memory_swap {
+                return Err(anyhow::anyhow!(
+                    "O limite de memória deve ser menor que o limite de memória + swap atual"
+                ));
+            }
+        }
+
+        if let Some(cpu_shares) = request.cpu_shares {
+            if cpu_shares != 0 && cpu_shares < 2 {
+                return Err(anyhow::anyhow!("CPU shares deve ser 0 ou pelo menos 2"));
+            }
+        }
+
+        if let Some(cpu_period) = request.cpu_period {
+            if cpu_period != 0 && !(1_000..=1_000_000).contains(&cpu_period) {
+                return Err(anyhow::anyhow!(
+                    "O período de CPU deve estar entre 1000 e 1000000 microssegundos"
+                ));
+            }
+        }
+
+        if let Some(cpu_quota) = request.cpu_quota {
+            if cpu_quota != -1 && cpu_quota != 0 && cpu_quota < 1_000 {
+                return Err(anyhow::anyhow!(
+                    "A cota de CPU deve ser de pelo menos 1000 microssegundos"
+                ));
+            }
+        }
+
+        if let Some(cpuset) = request.cpuset_cpus.as_deref() {
+            let online_cpus = self
+                .docker
+                .info()
+                .await
+                .context("Falha ao obter informações do Docker")?
+                .ncpu
+                .unwrap_or(0);
+            validate_cpuset(cpuset, online_cpus)?;
+        }
+
+        if let Some(pids_limit) = request.pids_limit {
+            if pids_limit < -1 {
+                return Err(anyhow::anyhow!("Limite de processos inválido"));
+            }
+        }
+
+        if let Some(max_retries) = request.restart_max_retries {
+            if max_retries < 0 {
+                return Err(anyhow::anyhow!(
+                    "O número máximo de tentativas não pode ser negativo"
+                ));
+            }
+            let policy = request
+                .restart_policy
+                .as_deref()
+                .unwrap_or(current.restart_policy.as_str());
+            if policy != "on-failure" && max_retries > 0 {
+                return Err(anyhow::anyhow!(
+                    "Tentativas máximas só se aplicam à política on-failure"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // Executa uma ação em vários containers com paralelismo limitado
+    pub async fn bulk_action(
+        &self,
+        request: BulkActionRequest,
+        on_progress: impl Fn(BulkActionProgress),
+    ) -> Result<Vec<BulkActionResult>> {
+        use futures_util::StreamExt;
+
+        let action = request.action.as_str();
+        if !matches!(
+            action,
+            "start" | "stop" | "restart" | "pause" | "unpause" | "remove"
+        ) {
+            return Err(anyhow::anyhow!("Ação inválida: {}", action));
+        }
+
+        let mut container_ids = request.container_ids.clone();
+        if let Some(selector) = request
+            .label_selector
+            .as_deref()
+            .map(str::trim)
+            .filter(|selector| !selector.is_empty())
+        {
+            container_ids.extend(self.container_ids_by_label(selector).await?);
+        }
+        container_ids.sort();
+        container_ids.dedup();
+
+        if container_ids.is_empty() {
+            return Err(anyhow::anyhow!("Nenhum container selecionado"));
+        }
+
+        let total = container_ids.len();
+        let concurrency = request
+            .concurrency
+            .unwrap_or(BULK_DEFAULT_CONCURRENCY)
+            .clamp(1, BULK_MAX_CONCURRENCY);
+
+        let mut stream = futures_util::stream::iter(container_ids)
+            .map(|container_id| async move {
+                let outcome = match action {
+                    "start" => self.start_container(&container_id).await,
+                    "stop" => self.stop_container(&container_id, None).await,
+                    "restart" => self.restart_container(&container_id, None).await,
+                    "pause" => self.pause_container(&container_id).await,
+                    "unpause" => self.unpause_container(&container_id).await,
+                    _ => {
+                        self.remove_container(&container_id, request.force.unwrap_or(false), false)
+                            .await
+                    }
+                };
+
+                BulkActionResult {
+                    container_id,
+                    success: outcome.is_ok(),
+                    error: outcome.err().map(|e| format!("{:#}", e)),
+                }
+            })
+            .buffer_unordered(concurrency);
+
+        let mut results = Vec::with_capacity(total);
+        while let Some(result) = stream.next().await {
+            on_progress(BulkActionProgress {
+                action: action.to_string(),
+                completed: results.len() + 1,
+                total,
+                result: result.clone(),
+            });
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
+    // Ids dos containers (ativos e parados) que possuem o label informado
+    async fn container_ids_by_label(&self, selector: &str) -> Result<Vec<String>> {
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                filters: Some(HashMap::from([(
+                    "label".to_string(),
+                    vec![selector.to_string()],
+                )])),
+                ..Default::default()
+            }))
+            .await
+            .context("Falha ao listar containers")?;
+
+        Ok(containers
+            .into_iter()
+            .filter_map(|container| container.id)
+            .collect())
+    }
+
+    // Recria um container com a mesma configuração, opcionalmente com imagem atualizada
+    pub async fn recreate_container(
+        &self,
+        request: RecreateContainerRequest,
+    ) -> Result<RecreateContainerResult> {
+        use bollard::query_parameters::InspectContainerOptions;
+
+        let inspect = self
+            .docker
+            .inspect_container(&request.container_id, None::<InspectContainerOptions>)
+            .await
+            .context(format!(
+                "Falha ao inspecionar container: {}",
+                request.container_id
+            ))?;
+
+        let old_id = inspect.id.clone().unwrap_or_default();
+        let name = inspect
+            .name
+            .clone()
+            .unwrap_or_default()
+            .trim_start_matches('/')
+            .to_string();
+        let was_running = inspect
+            .state
+            .as_ref()
+            .and_then(|state| state.running)
+            .unwrap_or(false);
+        let old_image_id = inspect.image.clone().unwrap_or_default();
+
+        // Com --rm o daemon apaga o container ao parar, e não sobraria nada para o rollback
+        let auto_remove = inspect
+            .host_config
+            .as_ref()
+            .and_then(|host_config| host_config.auto_remove)
+            .unwrap_or(false);
+        if auto_remove {
+            return Err(anyhow::anyhow!(
+                "Container '{}' foi criado com remoção automática (--rm) e seria apagado ao parar; recrie-o manualmente",
+                name
+            ));
+        }
+
+        let image = request
+            .image
+            .clone()
+            .filter(|image| !image.trim().is_empty())
+            .or_else(|| inspect.config.as_ref().and_then(|c| c.image.clone()))
+            .ok_or_else(|| anyhow::anyhow!("Imagem do container não encontrada"))?;
+
+        if request.pull || !self.image_exists(&image).await? {
+            self.pull_image(&image, None).await?;
+        }
+
+        // Config da imagem antiga, para separar os padrões dela do que o usuário definiu
+        let old_image_config = self
+            .docker
+            .inspect_image(&old_image_id)
+            .await
+            .ok()
+            .and_then(|image| image.config);
+        let body = container_body_from_inspect(&inspect, old_image_config.as_ref(), &image);
+
+        // Tira o container antigo do caminho mantendo-o para rollback
+        if was_running {
+            self.stop_container(&old_id, None).await?;
+        }
+        let backup_name = format!("{}-old-{}", name, chrono::Utc::now().timestamp());
+        if let Err(e) = self.rename_container(&old_id, &backup_name).await {
+            if was_running {
+                let _ = self.start_container(&old_id).await;
+            }
+            return Err(e);
+        }
+
+        let created = self
+            .docker
+            .create_container(
+                Some(CreateContainerOptions {
+                    name: Some(name.clone()),
+                    ..Default::default()
+                }),
+                body,
+            )
+            .await;
+        let new_id = match created {
+            Ok(response) => response.id,
+            Err(e) => {
+                let rollback = self
+                    .rollback_recreate(&old_id, &name, None, was_running)
+                    .await;
+                return Err(recreate_error(
+                    "Falha ao criar o novo container",
+                    e.into(),
+                    rollback,
+                ));
+            }
+        };
+
+        if let Err(e) = self.start_container(&new_id).await {
+            let rollback = self
+                .rollback_recreate(&old_id, &name, Some(&new_id), was_running)
+                .await;
+            return Err(recreate_error(
+                "Falha ao iniciar o novo container",
+                e,
+                rollback,
+            ));
+        }
+
+        let old_container_removed =
+            !request.keep_old && self.remove_container(&old_id, true, false).await.is_ok();
+
+        let image_id = self
+            .docker
+            .inspect_container(&new_id, None::<InspectContainerOptions>)
+            .await
+            .ok()
+            .and_then(|inspect| inspect.image)
+            .unwrap_or_default();
+
+        Ok(RecreateContainerResult {
+            container_id: new_id,
+            old_container_id: old_id,
+            image,
+            image_updated: !image_id.is_empty() && image_id != old_image_id,
+            image_id,
+            old_container_removed,
+        })
+    }
+
+    // Desfaz uma recriação: remove o novo container e restaura o antigo
+    async fn rollback_recreate(
+        &self,
+        old_id: &str,
+        name: &str,
+        new_id: Option<&str>,
+        was_running: bool,
+    ) -> Result<()> {
+        if let Some(new_id) = new_id {
+            self.remove_container(new_id, true, false).await?;
+        }
+
+        self.rename_container(old_id, name).await?;
+
+        if was_running {
+            self.start_container(old_id).await?;
+        }
+
+        Ok(())
+    }
+
+    // Reconstrói o `docker run` e o serviço do compose equivalentes a um container
+    pub async fn container_run_snippets(
+        &self,
+        container_id: &str,
+        reveal_secrets: bool,
+    ) -> Result<ContainerRunSnippets> {
+        use bollard::query_parameters::InspectContainerOptions;
+
+        let inspect = self
+            .docker
+            .inspect_container(container_id, None::<InspectContainerOptions>)
+            .await
+            .context(format!("Falha ao inspecionar container: {}", container_id))?;
+
+        // A configuração da imagem permite omitir valores que já vêm dela
+        let image_ref = inspect
+            .image
+            .clone()
+            .or_else(|| inspect.config.as_ref().and_then(|c| c.image.clone()))
+            .unwrap_or_default();
+        let image_config = match self.docker.inspect_image(&image_ref).await {
+            Ok(image) => image.config,
+            Err(_) => None,
+        };
+
+        Ok(generate_snippets(
+            &inspect,
+            image_config.as_ref(),
+            reveal_secrets,
+        ))
+    }
+
+    // Variáveis de ambiente de um container (segredos mascarados, a menos que revelados)
+    pub async fn container_environment(
+        &self,
+        container_id: &str,
+        reveal_secrets: bool,
+    ) -> Result<Vec<EnvVar>> {
+        use bollard::query_parameters::InspectContainerOptions;
+
+        let inspect = self
+            .docker
+            .inspect_container(container_id, None::<InspectContainerOptions>)
+            .await
+            .context(format!("Falha ao inspecionar container: {}", container_id))?;
+
+        let mut vars: Vec<EnvVar> = inspect
+            .config
+            .and_then(|config| config.env)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|var| match var.split_once('=') {
+                Some((key, value)) => EnvVar {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                },
+                None => EnvVar {
+                    key: var,
+                    value: String::new(),
+                },
+            })
+            .collect();
+
+        if !reveal_secrets {
+            mask_env_vars(&mut vars);
+        }
+
+        Ok(vars)
+    }
+
+    // Status, sequência de falhas e últimas execuções do healthcheck
+    pub async fn container_health(
+        &self,
+        container_id: &str,
+        last: Option<usize>,
+    ) -> Result<ContainerHealth> {
+        use bollard::query_parameters::InspectContainerOptions;
+
+        let inspect = self
+            .docker
+            .inspect_container(container_id, None::<InspectContainerOptions>)
+            .await
+            .context(format!("Falha ao inspecionar container: {}", container_id))?;
+
+        let health = inspect.state.and_then(|state| state.health);
+        let Some(health) = health else {
+            return Ok(ContainerHealth {
+                container_id: inspect.id.unwrap_or_else(|| container_id.to_string()),
+                status: "none".to_string(),
+                failing_streak: 0,
+                probes: Vec::new(),
+            });
+        };
+
+        // O Docker guarda as execuções da mais antiga para a mais recente
+        let log = health.log.unwrap_or_default();
+        let last = last.unwrap_or(DEFAULT_HEALTH_PROBES);
+        let probes = log
+            .into_iter()
+            .rev()
+            .take(last)
+            .map(|probe| HealthProbe {
+                start: probe.start.map(|d| d.to_string()).unwrap_or_default(),
+                end: probe.end.map(|d| d.to_string()).unwrap_or_default(),
+                exit_code: probe.exit_code.unwrap_or_default(),
+                output: probe.output.unwrap_or_default().trim_end().to_string(),
+            })
+            .collect();
+
+        let status = health
+            .status
+            .map(|status| status.to_string())
+            .filter(|status| !status.is_empty())
+            .unwrap_or_else(|| "none".to_string());
+
+        Ok(ContainerHealth {
+            container_id: inspect.id.unwrap_or_else(|| container_id.to_string()),
+            status,
+            failing_streak: health.failing_streak.unwrap_or_default(),
+            probes,
+        })
+    }
+
+    // Stream de mudanças de saúde dos containers, a partir dos eventos do Docker
+    pub fn health_events(
+        &self,
+    ) -> impl futures_util::Stream<Item = Result<HealthChangeEvent>> + Send + 'static {
+        use bollard::query_parameters::EventsOptions;
+        use futures_util::StreamExt;
+
+        let mut filters = HashMap::new();
+        filters.insert("type".to_string(), vec!["container".to_string()]);
+        filters.insert("event".to_string(), vec!["health_status".to_string()]);
+
+        // O cliente é clonado para o stream não depender do DockerManager
+        let docker = self.docker.clone();
+        docker
+            .events(Some(EventsOptions {
+                filters: Some(filters),
+                ..Default::default()
+            }))
+            .filter_map(|event| async move {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(e) => {
+                        return Some(Err(anyhow::anyhow!(
+                            "Falha ao receber eventos do Docker: {}",
+                            e
+                        )))
+                    }
+                };
+
+                // A ação vem no formato "health_status: healthy"
+                let status = event
+                    .action
+                    .as_deref()?
+                    .strip_prefix("health_status:")?
+                    .trim()
+                    .to_string();
+                let actor = event.actor.unwrap_or_default();
+                let name = actor
+                    .attributes
+                    .unwrap_or_default()
+                    .remove("name")
+                    .unwrap_or_default();
+
+                Some(Ok(HealthChangeEvent {
+                    container_id: actor.id.unwrap_or_default(),
+                    name,
+                    status,
+                    time: event.time.unwrap_or_default(),
+                }))
+            })
+    }
+
+    // Portas do host publicadas por containers existentes, com o nome do container
+    async fn published_ports(&self) -> Result<Vec<(HostPortBinding, String)>> {
+        let containers = self
+            .docker
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                ..Default::default()
+            }))
+            .await
+            .context("Falha ao listar containers")?;
+
+        let mut published = Vec::new();
+        for container in containers {
+            let name = container
+                .names
+                .unwrap_or_default()
+                .first()
+                .map(|name| name.trim_start_matches('/').to_string())
+                .unwrap_or_else(|| container.id.clone().unwrap_or_default());
+
+            for port in container.ports.unwrap_or_default() {
+                if let Some(public_port) = port.public_port {
+                    let protocol = port
+                        .typ
+                        .map(|typ| typ.to_string())
+                        .unwrap_or_else(|| "tcp".to_string());
+                    let binding = HostPortBinding {
+                        host_ip: port.ip.unwrap_or_default(),
+                        host_port: public_port,
+                        protocol,
+                    };
+                    published.push((binding, name.clone()));
+                }
+            }
+        }
+
+        Ok(published)
+    }
+
+    // Verifica as portas pedidas contra containers e processos do host, sugerindo alternativas
+    pub async fn check_ports(&self, ports: &[PortMapping]) -> Result<PortCheckResult> {
+        let published = self.published_ports().await?;
+        // Portas aleatórias ou escolhidas pelo Docker dentro de um intervalo não são verificadas
+        let requested: Vec<HostPortBinding> = ports
+            .iter()
+            .flat_map(|p| {
+                let host_ip = p.host_ip.clone().unwrap_or_default();
+                let protocol = p.protocol.to_lowercase();
+                p.fixed_host_ports()
+                    .into_iter()
+                    .map(move |port| HostPortBinding {
+                        host_ip: host_ip.trim().to_string(),
+                        host_port: port,
+                        protocol: protocol.clone(),
+                    })
+            })
+            .collect();
+
+        // /proc/net e os binds de teste são I/O bloqueante
+        tokio::task::spawn_blocking(move || port_conflicts(requested, &published))
+            .await
+            .context("Falha ao verificar portas")
+    }
+
+    // Falha se alguma porta já estiver publicada por um container ou repetida no pedido
+    pub async fn ensure_ports_available(&self, ports: &[PortMapping]) -> Result<()> {
+        let check = self.check_ports(ports).await?;
+        if check.available {
+            return Ok(());
+        }
+
+        let details: Vec<String> = check
+            .conflicts
+            .iter()
+            .map(|c| {
+                let mut detail = format!("{}/{}", c.host_port, c.protocol);
+                match &c.used_by {
+                    Some(name) => detail.push_str(&format!(" (container {})", name)),
+                    None => detail.push_str(" (repetida)"),
+                }
+                if let Some(suggested) = c.suggested_port {
+                    detail.push_str(&format!(", sugestão: {}", suggested));
+                }
+                detail
+            })
+            .collect();
+        Err(anyhow::anyhow!(
+            "Portas do host já em uso: {}",
+            details.join("; ")
+        ))
+    }
+
+    // Encontra uma porta livre no host, a partir da preferida ou escolhida pelo sistema
+    pub async fn find_free_port(&self, protocol: &str, preferred: Option<u16>) -> Result<u16> {
+        let protocol = protocol.to_lowercase();
+        if !matches!(protocol.as_str(), "tcp" | "udp") {
+            return Err(anyhow::anyhow!("Protocolo não suportado: {}", protocol));
+        }
+
+        let published = self.published_ports().await?;
+
+        tokio::task::spawn_blocking(move || {
+            let bound = host_ports::bound_ports(&protocol);
+
+            // Sem IP informado vale para todas as interfaces
+            let any_interface = |host_port| HostPortBinding {
+                host_ip: String::new(),
+                host_port,
+                protocol: protocol.clone(),
+            };
+
+            if let Some(preferred) = preferred.filter(|port| *port > 0) {
+                if let Some(port) =
+                    next_free_port(&any_interface(preferred), &published, &[], &bound)
+                {
+                    return Ok(port);
+                }
+            }
+
+            // Porta efêmera do sistema, descartando as já publicadas por containers
+            for _ in 0..10 {
+                if let Some(port) = host_ports::os_assigned_port(&protocol) {
+                    let candidate = any_interface(port);
+                    if !published
+                        .iter()
+                        .any(|(binding, _)| binding.collides_with(&candidate))
+                    {
+                        return Ok(port);
+                    }
+                }
+            }
+
+            Err(anyhow::anyhow!("Nenhuma porta livre encontrada"))
+        })
+        .await
+        .context("Falha ao procurar porta livre")?
+    }
+}
+
+// Guarda a linha no final dos logs e repassa ao callback
+fn push_task_line(
+    tail: &mut std::collections::VecDeque<String>,
+    tail_lines: usize,
+    container_id: &str,
+    line: &str,
+    stream: &str,
+    on_log: &impl Fn(TaskLogLine),
+) {
+    if tail_lines > 0 {
+        if tail.len() == tail_lines {
+            tail.pop_front();
+        }
+        tail.push_back(line.to_string());
+    }
+
+    on_log(TaskLogLine {
+        container_id: container_id.to_string(),
+        stream: stream.to_string(),
+        line: line.to_string(),
+    });
+}
+
+// Separa "registry:5000/repo:tag" em ("registry:5000/repo", Some("tag")); digests são ignorados
+fn split_image_reference(reference: &str) -> (String, Option<String>) {
+    let reference = reference.trim();
+    let reference = reference.split('@').next().unwrap_or(reference);
+    let last_segment_start = reference.rfind('/').map(|p| p + 1).unwrap_or(0);
+
+    match reference[last_segment_start..].rfind(':') {
+        Some(position) => {
+            let split = last_segment_start + position;
+            (
+                reference[..split].to_string(),
+                Some(reference[split + 1..].to_string()).filter(|tag| !tag.is_empty()),
+            )
+        }
+        None => (reference.to_string(), None),
+    }
+}
+
+// Filtro de label do prune: "chave", "chave=valor" ou, com "!", a negação deles
+fn label_filter_matches(labels: &HashMap<String, String>, filter: &str) -> bool {
+    let (negated, filter) = match filter.strip_prefix('!') {
+        Some(rest) => (true, rest),
+        None => (false, filter),
+    };
+    let matches = match filter.split_once('=') {
+        Some((key, value)) => labels.get(key).is_some_and(|label| label == value),
+        None => labels.contains_key(filter),
+    };
+    matches != negated
+}
+
+// Erro de push com dica para registries HTTP (comum em redes isoladas)
+fn push_error(image: &str, error: &str) -> anyhow::Error {
+    if error.contains("server gave HTTP response to HTTPS client") {
+        return anyhow::anyhow!(
+            "Falha ao enviar '{}': o registry usa HTTP; adicione-o em \"insecure-registries\" no daemon.json ({})",
+            image,
+            error
+        );
+    }
+    anyhow::anyhow!("Falha ao enviar '{}': {}", image, error)
+}
+
+// Converte o "created_by" do histórico na instrução do Dockerfile correspondente
+fn history_instruction(created_by: &str) -> String {
+    let text = created_by.trim();
+    let text = text.strip_suffix("# buildkit").unwrap_or(text).trim();
+
+    // Builds com ARG prefixam o comando com "|N CHAVE=valor ..."
+    let text = match text.strip_prefix('|') {
+        Some(rest) => rest
+            .find("/bin/sh -c")
+            .map(|position| &rest[position..])
+            .unwrap_or(text),
+        None => text,
+    };
+
+    if let Some(rest) = text.strip_prefix("/bin/sh -c #(nop)") {
+        return rest.trim().to_string();
+    }
+    if let Some(rest) = text.strip_prefix("/bin/sh -c") {
+        return format!("RUN {}", rest.trim());
+    }
+
+    text.to_string()
+}
+
+// Extrai a saúde do texto de status ("Up 5 minutes (healthy)", "(health: starting)")
+fn health_from_status(status: &str) -> Option<String> {
+    let start = status.rfind('(')?;
+    let inner = status[start + 1..].strip_suffix(')')?;
+    let health = inner.strip_prefix("health: ").unwrap_or(inner);
+
+    matches!(health, "starting" | "healthy" | "unhealthy").then(|| health.to_string())
+}
+
+// Valida protocolo, IP e intervalos de um mapeamento de porta
+fn validate_port_mapping(port_map: &PortMapping) -> Result<()> {
+    if !matches!(
+        port_map.protocol.to_lowercase().as_str(),
+        "tcp" | "udp" | "sctp"
+    ) {
+        return Err(anyhow::anyhow!(
+            "Protocolo não suportado: {}",
+            port_map.protocol
+        ));
+    }
+
+    if let Some(ip) = port_map
+        .host_ip
+        .as_deref()
+        .filter(|ip| !ip.trim().is_empty())
+    {
+        if ip.parse::<std::net::IpAddr>().is_err() {
+            return Err(anyhow::anyhow!("IP do host inválido: {}", ip));
+        }
+    }
+
+    if port_map.container_port == 0 || port_map.host_port == Some(0) {
+        return Err(anyhow::anyhow!("Porta inválida: 0"));
+    }
+
+    if port_map
+        .container_port_end
+        .is_some_and(|end| end < port_map.container_port)
+    {
+        return Err(anyhow::anyhow!(
+            "Intervalo de portas do container inválido: {}",
+            port_map.container_port
+        ));
+    }
+
+    match (port_map.host_port, port_map.host_port_end) {
+        (None, Some(_)) => Err(anyhow::anyhow!(
+            "Intervalo de portas do host sem porta inicial"
+        )),
+        (Some(start), Some(end)) if end < start => Err(anyhow::anyhow!(
+            "Intervalo de portas do host inválido: {}-{}",
+            start,
+            end
+        )),
+        // Intervalo no container exige intervalo do mesmo tamanho no host (ou porta aleatória)
+        (Some(start), end) if port_map.container_port_end.is_some() => {
+            let container_count = port_map.container_ports().len();
+            let host_count = usize::from(end.unwrap_or(start) - start) + 1;
+            if container_count != host_count {
+                return Err(anyhow::anyhow!(
+                    "Intervalos do host e do container devem ter o mesmo tamanho"
+                ));
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+// Conflitos das portas pedidas: containers e repetições bloqueiam a criação; processos
+// do host viram apenas aviso
+fn port_conflicts(
+    requested: Vec<HostPortBinding>,
+    published: &[(HostPortBinding, String)],
+) -> PortCheckResult {
+    let mut bound: HashMap<String, HashSet<u16>> = HashMap::new();
+    let mut seen: Vec<HostPortBinding> = Vec::new();
+    let mut reserved: Vec<HostPortBinding> = requested.clone();
+    let mut conflicts = Vec::new();
+    let mut host_warnings = Vec::new();
+
+    for binding in requested {
+        let bound_ports = bound
+            .entry(binding.protocol.clone())
+            .or_insert_with(|| host_ports::bound_ports(&binding.protocol));
+
+        let (source, used_by) = if seen.iter().any(|other| other.collides_with(&binding)) {
+            ("request", None)
+        } else if let Some((_, container)) = published
+            .iter()
+            .find(|(other, _)| other.collides_with(&binding))
+        {
+            ("container", Some(container.clone()))
+        } else if host_ports::is_port_in_use(binding.host_port, &binding.protocol, bound_ports) {
+            ("host", None)
+        } else {
+            seen.push(binding);
+            continue;
+        };
+
+        let suggested_port = next_free_port(
+            &HostPortBinding {
+                host_port: binding.host_port.saturating_add(1),
+                ..binding.clone()
+            },
+            published,
+            &reserved,
+            bound_ports,
+        );
+        if let Some(suggested) = suggested_port {
+            reserved.push(HostPortBinding {
+                host_port: suggested,
+                ..binding.clone()
+            });
+        }
+
+        let conflict = PortConflict {
+            host_port: binding.host_port,
+            protocol: binding.protocol.clone(),
+            source: source.to_string(),
+            used_by,
+            suggested_port,
+        };
+        if source == "host" {
+            host_warnings.push(conflict);
+        } else {
+            conflicts.push(conflict);
+        }
+        seen.push(binding);
+    }
+
+    PortCheckResult {
+        available: conflicts.is_empty(),
+        conflicts,
+        host_warnings,
+    }
+}
+
+// Primeira porta livre a partir de `start.host_port` no mesmo IP e protocolo, ignorando as
+// publicadas e as reservadas
+fn next_free_port(
+    start: &HostPortBinding,
+    published: &[(HostPortBinding, String)],
+    reserved: &[HostPortBinding],
+    bound: &HashSet<u16>,
+) -> Option<u16> {
+    let end = start.host_port.saturating_add(PORT_SEARCH_LIMIT);
+    (start.host_port.max(1)..=end).find(|port| {
+        let candidate = HostPortBinding {
+            host_port: *port,
+            ..start.clone()
+        };
+        !published
+            .iter()
+            .any(|(binding, _)| binding.collides_with(&candidate))
+            && !reserved
+                .iter()
+                .any(|binding| binding.collides_with(&candidate))
+            && !host_ports::is_port_in_use(*port, &candidate.protocol, bound)
+    })
+}
+
+fn is_wildcard_ip(ip: &str) -> bool {
+    ip.is_empty()
+        || ip
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_unspecified())
+}
+
+// Compara IPs pelo valor ("::1" e "0:0::1" são o mesmo endereço)
+fn same_ip(a: &str, b: &str) -> bool {
+    match (a.parse::<std::net::IpAddr>(), b.parse::<std::net::IpAddr>()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+// Caminho único para arquivos tar temporários
+fn temp_archive_path() -> PathBuf {
+    std::env::temp_dir().join(format!("docker-ui-{:016x}.tar", rand::random::<u64>()))
+}
+
+// Consultas de stat simultâneas ao calcular o tamanho das alterações
+const CHANGE_STAT_CONCURRENCY: usize = 8;
+
+// Sinais aceitos para processos e containers
+const SUPPORTED_SIGNALS: &[&str] = &[
+    "HUP", "INT", "QUIT", "KILL", "USR1", "USR2", "TERM", "CONT", "STOP", "WINCH",
+];
+
+// Normaliza um sinal ("SIGTERM", "term" ou "15") para o nome sem prefixo
+fn normalize_signal(signal: &str) -> Result<String> {
+    let upper = signal.trim().to_uppercase();
+    let name = upper.strip_prefix("SIG").unwrap_or(&upper).to_string();
+
+    let name = match name.as_str() {
+        "1" => "HUP".to_string(),
+        "2" => "INT".to_string(),
+        "3" => "QUIT".to_string(),
+        "9" => "KILL".to_string(),
+        "10" => "USR1".to_string(),
+        "12" => "USR2".to_string(),
+        "15" => "TERM".to_string(),
+        "18" => "CONT".to_string(),
+        "19" => "STOP".to_string(),
+        "28" => "WINCH".to_string(),
+        _ => name,
+    };
+
+    if SUPPORTED_SIGNALS.contains(&name.as_str()) {
+        Ok(name)
+    } else {
+        Err(anyhow::anyhow!("Sinal não suportado: {}", signal))
+    }
+}
+
+// Valida o tempo de espera antes de forçar a parada (-1 aguarda indefinidamente)
+fn validate_stop_timeout(timeout: Option<i32>) -> Result<Option<i32>> {
+    match timeout {
+        Some(t) if t < -1 => Err(anyhow::anyhow!("Tempo de espera inválido: {}", t)),
+        _ => Ok(timeout),
+    }
+}
+
+// Idade mínima da limpeza de imagens, usada igualmente na prévia e no prune: o daemon lê
+// "until" como duração do Go, que não passa de ~292 anos
+fn validate_prune_age(hours: Option<u64>) -> Result<Option<u64>> {
+    match hours {
+        Some(h) if h > MAX_PRUNE_AGE_HOURS => Err(anyhow::anyhow!(
+            "Idade inválida: {} horas (máximo {})",
+            h,
+            MAX_PRUNE_AGE_HOURS
+        )),
+        _ => Ok(hours),
+    }
+}
+
+// Nomes de container seguem o padrão do Docker: [a-zA-Z0-9][a-zA-Z0-9_.-]*
+fn is_valid_container_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) if first.is_ascii_alphanumeric() => {
+            chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+        }
+        _ => false,
+    }
+}
+
+// O /proc desta máquina só descreve o container se o daemon roda nela (não vale para
+// DOCKER_HOST remoto nem para o Docker Desktop, que usa uma VM): confere pelo cgroup
+// do processo principal
+fn container_visible_in_local_proc(container_id: &str, main_pid: i64) -> bool {
+    if container_id.is_empty() || main_pid <= 0 {
+        return false;
+    }
+    std::fs::read_to_string(format!("/proc/{}/cgroup", main_pid))
+        .is_ok_and(|cgroup| cgroup.contains(container_id))
+}
+
+// Traduz o PID do host para o PID dentro do container via /proc (apenas Linux local)
+fn namespace_pid(host_pid: &str) -> Option<String> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", host_pid)).ok()?;
+    status
+        .lines()
+        .find(|line| line.starts_with("NSpid:"))
+        .and_then(|line| line.split_whitespace().last())
+        .map(|pid| pid.to_string())
+}
+
+// Monta a configuração de criação a partir do inspect de um container existente.
+// Do Config só vão os valores que o usuário definiu (diferentes da imagem antiga), para
+// que os padrões da nova imagem (ENV, CMD, labels...) prevaleçam
+fn container_body_from_inspect(
+    inspect: &bollard::models::ContainerInspectResponse,
+    old_image_config: Option<&bollard::models::ImageConfig>,
+    image: &str,
+) -> ContainerCreateBody {
+    use bollard::models::{EndpointSettings, MountPointTypeEnum, NetworkingConfig};
+
+    let config = inspect.config.clone().unwrap_or_default();
+    let image_config = old_image_config.cloned().unwrap_or_default();
+    fn user_value<T: PartialEq>(value: Option<T>, image_value: &Option<T>) -> Option<T> {
+        value.filter(|value| Some(value) != image_value.as_ref())
+    }
+
+    let image_env = image_config.env.clone().unwrap_or_default();
+    let env: Vec<String> = config
+        .env
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|var| !image_env.contains(var))
+        .collect();
+
+    let image_labels = image_config.labels.clone().unwrap_or_default();
+    let labels: HashMap<String, String> = config
+        .labels
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(key, value)| image_labels.get(key) != Some(value))
+        .collect();
+
+    let image_volumes = image_config.volumes.clone().unwrap_or_default();
+    let volumes: HashMap<String, HashMap<(), ()>> = config
+        .volumes
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(path, _)| !image_volumes.contains_key(path))
+        .collect();
+
+    let image_ports = image_config.exposed_ports.clone().unwrap_or_default();
+    let exposed_ports: HashMap<String, HashMap<(), ()>> = config
+        .exposed_ports
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(port, _)| !image_ports.contains_key(port))
+        .collect();
+
+    // Com ENTRYPOINT próprio o Docker descarta o CMD da imagem, então o CMD vai como está
+    let entrypoint = user_value(config.entrypoint.clone(), &image_config.entrypoint);
+    let cmd = if entrypoint.is_some() {
+        config.cmd.clone()
+    } else {
+        user_value(config.cmd.clone(), &image_config.cmd)
+    };
+    let mut host_config = inspect.host_config.clone().unwrap_or_default();
+    let old_id = inspect.id.clone().unwrap_or_default();
+
+    // Hostname gerado pelo Docker é o id curto do container antigo
+    let hostname = config
+        .hostname
+        .clone()
+        .filter(|hostname| !old_id.starts_with(hostname.as_str()));
+
+    // Volumes anônimos não aparecem no HostConfig; reaproveita-os para não perder dados
+    let mut binds = host_config.binds.clone().unwrap_or_default();
+    let covered: Vec<String> = binds
+        .iter()
+        .filter_map(|bind| bind.split(':').nth(1).map(|dest| dest.to_string()))
+        .chain(
+            host_config
+                .mounts
+                .iter()
+                .flatten()
+                .filter_map(|mount| mount.target.clone()),
+        )
+        .collect();
+    for mount in inspect.mounts.iter().flatten() {
+        if mount.typ != Some(MountPointTypeEnum::VOLUME) {
+            continue;
+        }
+        if let (Some(volume), Some(dest)) = (&mount.name, &mount.destination) {
+            if !covered.contains(dest) {
+                let mode = if mount.rw == Some(false) { ":ro" } else { "" };
+                binds.push(format!("{}:{}{}", volume, dest, mode));
+            }
+        }
+    }
+    if !binds.is_empty() {
+        host_config.binds = Some(binds);
+    }
+
+    // Mantém redes, aliases e IPs fixos, descartando dados de runtime
+    let short_id: String = old_id.chars().take(12).collect();
+    let endpoints: HashMap<String, EndpointSettings> = inspect
+        .network_settings
+        .as_ref()
+        .and_then(|settings| settings.networks.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(network, endpoint)| {
+            let aliases = endpoint.aliases.map(|aliases| {
+                aliases
+                    .into_iter()
+                    .filter(|alias| *alias != short_id)
+                    .collect::<Vec<_>>()
+            });
+            (
+                network,
+                EndpointSettings {
+                    ipam_config: endpoint.ipam_config,
+                    links: endpoint.links,
+                    aliases,
+                    driver_opts: endpoint.driver_opts,
+                    gw_priority: endpoint.gw_priority,
+                    ..Default::default()
+                },
+            )
+        })
+        .collect();
+
+    ContainerCreateBody {
+        hostname,
+        domainname: config.domainname,
+        user: user_value(config.user, &image_config.user),
+        attach_stdin: config.attach_stdin,
+        attach_stdout: config.attach_stdout,
+        attach_stderr: config.attach_stderr,
+        exposed_ports: Some(exposed_ports).filter(|ports| !ports.is_empty()),
+        tty: config.tty,
+        open_stdin: config.open_stdin,
+        stdin_once: config.stdin_once,
+        env: Some(env).filter(|env| !env.is_empty()),
+        cmd,
+        healthcheck: user_value(config.healthcheck, &image_config.healthcheck),
+        image: Some(image.to_string()),
+        volumes: Some(volumes).filter(|volumes| !volumes.is_empty()),
+        working_dir: user_value(config.working_dir, &image_config.working_dir),
+        entrypoint,
+        network_disabled: config.network_disabled,
+        labels: Some(labels).filter(|labels| !labels.is_empty()),
+        stop_signal: user_value(config.stop_signal, &image_config.stop_signal),
+        stop_timeout: config.stop_timeout,
+        shell: user_value(config.shell, &image_config.shell),
+        host_config: Some(host_config),
+        networking_config: if endpoints.is_empty() {
+            None
+        } else {
+            Some(NetworkingConfig {
+                endpoints_config: Some(endpoints),
+            })
+        },
+        ..Default::default()
+    }
+}
+
+// Erro de recriação indicando se o container original foi restaurado
+fn recreate_error(message: &str, error: anyhow::Error, rollback: Result<()>) -> anyhow::Error {
+    match rollback {
+        Ok(_) => anyhow::anyhow!(
+            "{}: {:#}. O container original foi restaurado.",
+            message,
+            error
+        ),
+        Err(rollback_error) => anyhow::anyhow!(
+            "{}: {:#}. Falha ao restaurar o container original: {:#}",
+            message,
+            error,
+            rollback_error
+        ),
+    }
+}
+
+// Converte o nome da política de restart no formato da API
+fn restart_policy_from_name(
+    name: &str,
+    max_retries: Option<i64>,
+) -> Option<bollard::models::RestartPolicy> {
+    use bollard::models::{RestartPolicy, RestartPolicyNameEnum};
+
+    match name {
+        "always" => Some(RestartPolicy {
+            name: Some(RestartPolicyNameEnum::ALWAYS),
+            maximum_retry_count: None,
+        }),
+        "unless-stopped" => Some(RestartPolicy {
+            name: Some(RestartPolicyNameEnum::UNLESS_STOPPED),
+            maximum_retry_count: None,
+        }),
+        "on-failure" => Some(RestartPolicy {
+            name: Some(RestartPolicyNameEnum::ON_FAILURE),
+            maximum_retry_count: Some(max_retries.unwrap_or(3)),
+        }),
+        "no" | "" => Some(RestartPolicy {
+            name: Some(RestartPolicyNameEnum::NO),
+            maximum_retry_count: None,
+        }),
+        _ => None,
+    }
+}
+
+// Valida um cpuset ("0-3,5") contra o número de CPUs disponíveis
+fn validate_cpuset(cpuset: &str, online_cpus: i64) -> Result<()> {
+    let invalid = || anyhow::anyhow!("Conjunto de CPUs inválido: {}", cpuset);
+
+    for part in cpuset.split(',').map(str::trim) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (start, end),
+            None => (part, part),
+        };
+        let start: i64 = start.trim().parse().map_err(|_| invalid())?;
+        let end: i64 = end.trim().parse().map_err(|_| invalid())?;
+
+        if start > end {
+            return Err(invalid());
+        }
+        if online_cpus > 0 && end >= online_cpus {
+            return Err(anyhow::anyhow!(
+                "CPU {} não disponível (o host possui {} CPUs)",
+                end,
+                online_cpus
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+// Insere um caminho alterado na árvore, criando os diretórios intermediários
+fn insert_change_node(
+    nodes: &mut Vec<FileChangeNode>,
+    parent_path: &str,
+    components: &[&str],
+    kind: &str,
+    size: Option<u64>,
+) {
+    let name = components[0];
+    let path = format!("{}/{}", parent_path, name);
+
+    let index = match nodes.iter().position(|node| node.name == name) {
+        Some(index) => index,
+        None => {
+            nodes.push(FileChangeNode {
+                name: name.to_string(),
+                path: path.clone(),
+                kind: None,
+                size: None,
+                children: Vec::new(),
+            });
+            nodes.len() - 1
+        }
+    };
+
+    let node = &mut nodes[index];
+    if components.len() == 1 {
+        node.kind = Some(kind.to_string());
+        node.size = size;
+    } else {
+        insert_change_node(&mut node.children, &path, &components[1..], kind, size);
+    }
+}
+
+// Caminhos das folhas (sem filhos) que ainda existem no container
+fn collect_change_leaves(nodes: &[FileChangeNode], leaves: &mut Vec<String>) {
+    for node in nodes {
+        if !node.children.is_empty() {
+            collect_change_leaves(&node.children, leaves);
+        } else if node.kind.as_deref() != Some("deleted") {
+            leaves.push(node.path.clone());
+        }
+    }
+}
+
+fn set_change_sizes(nodes: &mut [FileChangeNode], sizes: &HashMap<String, u64>) {
+    for node in nodes {
+        if node.children.is_empty() {
+            node.size = sizes.get(&node.path).copied();
+        } else {
+            set_change_sizes(&mut node.children, sizes);
+        }
+    }
+}
+
+// Propaga para os diretórios a soma dos tamanhos dos filhos; sem o tamanho de algum
+// filho, o diretório também fica sem tamanho
+fn sum_change_sizes(node: &mut FileChangeNode) -> Option<u64> {
+    if node.children.is_empty() {
+        // Removidos não ocupam espaço no container
+        if node.kind.as_deref() == Some("deleted") {
+            return Some(0);
+        }
+        return node.size;
+    }
+
+    let sizes: Vec<Option<u64>> = node.children.iter_mut().map(sum_change_sizes).collect();
+    node.size = sizes.into_iter().sum();
+    node.size
+}
+
+// Converte uma linha de `stat -c '%F|%s|%Y|%f|%n'` em entrada de diretório
+fn parse_stat_line(line: &str) -> Option<ContainerFileEntry> {
+    let mut parts = line.splitn(5, '|');
+    let file_type = parts.next()?;
+    let size = parts.next()?.parse::<u64>().unwrap_or(0);
+    let modified = parts.next()?.parse::<i64>().unwrap_or(0);
+    let raw_mode = u32::from_str_radix(parts.next()?, 16).unwrap_or(0);
+    let path = parts.next()?.to_string();
+
+    let kind = match file_type {
+        "directory" => "directory",
+        "symbolic link" => "symlink",
+        t if t.starts_with("regular") => "file",
+        _ => "other",
+    };
+
+    Some(ContainerFileEntry {
+        name: path.rsplit('/').next().unwrap_or_default().to_string(),
+        path,
+        kind: kind.to_string(),
+        size,
+        mode: raw_mode & 0o7777,
+        modified,
+        link_target: None,
+    })
+}
+
+// Lê apenas os filhos diretos do diretório a partir do tar do container (o conteúdo dos
+// arquivos é lido só para ser pulado, sem ir para a memória ou o disco)
+fn list_archive_children(
+    reader: &mut dyn std::io::Read,
+    base_dir: &str,
+) -> Result<Vec<ContainerFileEntry>> {
+    let mut archive = tar::Archive::new(reader);
+
+    // O tar de "/" não tem diretório raiz; nos demais casos o primeiro nível é o próprio diretório
+    let depth = if base_dir.is_empty() { 1 } else { 2 };
+    let mut entries = Vec::new();
+
+    for entry in archive.entries().context("Arquivo tar inválido")? {
+        let entry = entry.context("Arquivo tar inválido")?;
+        let entry_path = entry.path().context("Arquivo tar inválido")?.to_path_buf();
+        let components: Vec<String> = entry_path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+                _ => None,
+            })
+            .collect();
+
+        if components.len() != depth {
+            continue;
+        }
+
+        let header = entry.header();
+        let entry_type = header.entry_type();
+        let kind = if entry_type.is_dir() {
+            "directory"
+        } else if entry_type.is_symlink() {
+            "symlink"
+        } else if entry_type.is_file() {
+            "file"
+        } else {
+            "other"
+        };
+
+        let name = components[depth - 1].clone();
+        entries.push(ContainerFileEntry {
+            path: format!("{}/{}", base_dir, name),
+            name,
+            kind: kind.to_string(),
+            size: header.size().unwrap_or(0),
+            mode: header.mode().unwrap_or(0) & 0o7777,
+            modified: header.mtime().unwrap_or(0) as i64,
+            link_target: entry
+                .link_name()
+                .ok()
+                .flatten()
+                .map(|target| target.to_string_lossy().to_string()),
+        });
+    }
+
+    Ok(entries)
+}
+
+// Extrai o tar baixado do container no destino do host
+fn extract_archive(archive_path: &Path, target: &Path) -> Result<()> {
+    // Destino já é um diretório: extrai dentro dele mantendo o nome original
+    if target.is_dir() {
+        unpack_archive_in(archive_path, target)?;
+        return Ok(());
+    }
+
+    // Caso contrário extrai em um diretório temporário ao lado do destino e renomeia a
+    // entrada raiz (o arquivo ou diretório baixado) para o destino
+    let parent = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    std::fs::create_dir_all(parent)
+        .context(format!("Falha ao criar diretório {}", parent.display()))?;
+
+    let staging = parent.join(format!(".docker-ui-{:016x}", rand::random::<u64>()));
+    std::fs::create_dir(&staging)
+        .context(format!("Falha ao criar diretório {}", staging.display()))?;
+
+    let result = unpack_archive_in(archive_path, &staging).and_then(|root| {
+        let root = root.ok_or_else(|| anyhow::anyhow!("O arquivo baixado está vazio"))?;
+        std::fs::rename(staging.join(root), target)
+            .context(format!("Falha ao mover arquivos para {}", target.display()))
+    });
+    let _ = std::fs::remove_dir_all(&staging);
+
+    result
+}
+
+// Extrai entrada por entrada com `unpack_in`, que recusa caminhos que saiam de
+// `destination` (inclusive através de symlinks), e devolve o nome da entrada raiz
+fn unpack_archive_in(archive_path: &Path, destination: &Path) -> Result<Option<PathBuf>> {
+    let file = std::fs::File::open(archive_path).context("Falha ao abrir arquivo temporário")?;
+    let mut archive = tar::Archive::new(file);
+    let mut root: Option<PathBuf> = None;
+
+    for entry in archive.entries().context("Arquivo tar inválido")? {
+        let mut entry = entry.context("Arquivo tar inválido")?;
+        let entry_path = entry.path().context("Arquivo tar inválido")?.to_path_buf();
+
+        // Links cujo alvo sai do destino não são criados
+        let link_base = match entry.header().entry_type() {
+            tar::EntryType::Symlink => {
+                Some(entry_path.parent().unwrap_or(Path::new("")).to_path_buf())
+            }
+            tar::EntryType::Link => Some(PathBuf::new()),
+            _ => None,
+        };
+        if let Some(link_base) = link_base {
+            let link_target = entry
+                .link_name()
+                .context("Arquivo tar inválido")?
+                .map(|target| target.to_path_buf())
+                .unwrap_or_default();
+            if !link_stays_inside(&link_base, &link_target) {
+                continue;
+            }
+        }
+
+        if root.is_none() {
+            root = entry_path
+                .components()
+                .find_map(|component| match component {
+                    Component::Normal(name) => Some(PathBuf::from(name)),
+                    _ => None,
+                });
+        }
+
+        entry
+            .unpack_in(destination)
+            .context(format!("Falha ao extrair {}", entry_path.display()))?;
+    }
+
+    Ok(root)
+}
+
+// Verifica, sem acessar o disco, se `base/link` continua dentro da raiz da extração
+fn link_stays_inside(base: &Path, link: &Path) -> bool {
+    let mut depth = 0usize;
+    for component in base.join(link).components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir => match depth.checked_sub(1) {
+                Some(parent) => depth = parent,
+                None => return false,
+            },
+            Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+    true
+}
+
+// Empacota arquivos e diretórios do host em um tar para envio ao container
+fn build_archive(sources: &[PathBuf], archive_path: &Path) -> Result<()> {
+    let file = std::fs::File::create(archive_path).context("Falha ao criar arquivo temporário")?;
+    let mut builder = tar::Builder::new(file);
+    builder.follow_symlinks(false);
+
+    for source in sources {
+        let name = source
+            .file_name()
+            .ok_or_else(|| anyhow::anyhow!("Caminho inválido: {}", source.display()))?;
+
+        if source.is_dir() {
+            builder
+                .append_dir_all(name, source)
+                .context(format!("Falha ao empacotar {}", source.display()))?;
+        } else {
+            builder
+                .append_path_with_name(source, name)
+                .context(format!("Falha ao empacotar {}", source.display()))?;
+        }
+    }
+
+    builder
+        .finish()
+        .context("Falha ao finalizar arquivo temporário")?;
+    Ok(())
 }
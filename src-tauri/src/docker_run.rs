@@ -0,0 +1,1104 @@
+// Conversão entre containers e comandos `docker run` / serviços do compose
+use crate::docker::{CreateContainerRequest, EnvVar, PortMapping, VolumeMapping};
+use crate::environment::{mask_env_pairs, parse_env_file};
+use anyhow::Result;
+use bollard::models::{ContainerInspectResponse, ImageConfig};
+use serde::{Deserialize, Serialize};
+
+// Trechos gerados a partir de um container existente
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContainerRunSnippets {
+    pub docker_run: String,
+    pub compose: String,
+}
+
+// Resultado da importação de um `docker run`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DockerRunImport {
+    pub request: CreateContainerRequest,
+    pub warnings: Vec<String>,
+}
+
+// Opções do `docker run` que recebem valor (para ignorar corretamente as não suportadas)
+const RUN_VALUE_FLAGS: &[&str] = &[
+    "--add-host",
+    "--annotation",
+    "--attach",
+    "--blkio-weight",
+    "--blkio-weight-device",
+    "--cap-add",
+    "--cap-drop",
+    "--cgroup-parent",
+    "--cgroupns",
+    "--cidfile",
+    "--cpu-period",
+    "--cpu-quota",
+    "--cpu-rt-period",
+    "--cpu-rt-runtime",
+    "--cpu-shares",
+    "--cpus",
+    "--cpuset-cpus",
+    "--cpuset-mems",
+    "--detach-keys",
+    "--device",
+    "--device-cgroup-rule",
+    "--device-read-bps",
+    "--device-read-iops",
+    "--device-write-bps",
+    "--device-write-iops",
+    "--dns",
+    "--dns-option",
+    "--dns-search",
+    "--domainname",
+    "--entrypoint",
+    "--env",
+    "--env-file",
+    "--expose",
+    "--gpus",
+    "--group-add",
+    "--health-cmd",
+    "--health-interval",
+    "--health-retries",
+    "--health-start-interval",
+    "--health-start-period",
+    "--health-timeout",
+    "--hostname",
+    "--ip",
+    "--ip6",
+    "--ipc",
+    "--isolation",
+    "--kernel-memory",
+    "--label",
+    "--label-file",
+    "--link",
+    "--link-local-ip",
+    "--log-driver",
+    "--log-opt",
+    "--mac-address",
+    "--memory",
+    "--memory-reservation",
+    "--memory-swap",
+    "--memory-swappiness",
+    "--mount",
+    "--name",
+    "--net",
+    "--network",
+    "--network-alias",
+    "--oom-score-adj",
+    "--pid",
+    "--pids-limit",
+    "--platform",
+    "--publish",
+    "--pull",
+    "--restart",
+    "--runtime",
+    "--security-opt",
+    "--shm-size",
+    "--stop-signal",
+    "--stop-timeout",
+    "--storage-opt",
+    "--sysctl",
+    "--tmpfs",
+    "--ulimit",
+    "--user",
+    "--userns",
+    "--uts",
+    "--volume",
+    "--volume-driver",
+    "--volumes-from",
+    "--workdir",
+];
+
+// Opções curtas que recebem valor e seus equivalentes longos
+const RUN_SHORT_VALUE_FLAGS: &[(char, &str)] = &[
+    ('a', "--attach"),
+    ('c', "--cpu-shares"),
+    ('e', "--env"),
+    ('h', "--hostname"),
+    ('l', "--label"),
+    ('m', "--memory"),
+    ('p', "--publish"),
+    ('u', "--user"),
+    ('v', "--volume"),
+    ('w', "--workdir"),
+];
+
+// Configuração normalizada de um container, usada para gerar os dois formatos
+#[derive(Debug, Default)]
+struct RunSpec {
+    name: String,
+    image: String,
+    auto_remove: bool,
+    tty: bool,
+    interactive: bool,
+    restart: Option<String>,
+    ports: Vec<String>,
+    volumes: Vec<String>,
+    mounts: Vec<String>,
+    tmpfs: Vec<String>,
+    env: Vec<(String, String)>,
+    labels: Vec<(String, String)>,
+    networks: Vec<(String, Vec<String>)>,
+    network_mode: Option<String>,
+    hostname: Option<String>,
+    user: Option<String>,
+    working_dir: Option<String>,
+    entrypoint: Option<Vec<String>>,
+    command: Option<Vec<String>>,
+    memory: Option<i64>,
+    memory_swap: Option<i64>,
+    memory_reservation: Option<i64>,
+    cpus: Option<f64>,
+    cpu_shares: Option<i64>,
+    cpu_quota: Option<i64>,
+    cpu_period: Option<i64>,
+    cpuset_cpus: Option<String>,
+    pids_limit: Option<i64>,
+    shm_size: Option<i64>,
+    privileged: bool,
+    cap_add: Vec<String>,
+    cap_drop: Vec<String>,
+    extra_hosts: Vec<String>,
+    dns: Vec<String>,
+}
+
+// Gera o comando `docker run` e o serviço do compose equivalentes ao container
+// (segredos do ambiente ficam mascarados, a menos que `reveal_secrets` seja pedido)
+pub fn generate_snippets(
+    inspect: &ContainerInspectResponse,
+    image_config: Option<&ImageConfig>,
+    reveal_secrets: bool,
+) -> ContainerRunSnippets {
+    let mut spec = run_spec_from_inspect(inspect, image_config);
+    if !reveal_secrets {
+        mask_env_pairs(&mut spec.env);
+    }
+
+    ContainerRunSnippets {
+        docker_run: render_docker_run(&spec),
+        compose: render_compose(&spec),
+    }
+}
+
+// Extrai do inspect apenas o que difere dos padrões do Docker e da imagem
+fn run_spec_from_inspect(
+    inspect: &ContainerInspectResponse,
+    image_config: Option<&ImageConfig>,
+) -> RunSpec {
+    let config = inspect.config.clone().unwrap_or_default();
+    let host_config = inspect.host_config.clone().unwrap_or_default();
+    let image_config = image_config.cloned().unwrap_or_default();
+    let id = inspect.id.clone().unwrap_or_default();
+    let short_id: String = id.chars().take(12).collect();
+    let name = inspect
+        .name
+        .clone()
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+
+    let mut spec = RunSpec {
+        image: config.image.clone().unwrap_or_default(),
+        auto_remove: host_config.auto_remove.unwrap_or(false),
+        tty: config.tty.unwrap_or(false),
+        interactive: config.open_stdin.unwrap_or(false),
+        ..Default::default()
+    };
+
+    // Política de restart
+    if let Some(policy) = host_config.restart_policy.as_ref() {
+        let policy_name = policy.name.map(|name| name.to_string()).unwrap_or_default();
+        spec.restart = match policy_name.as_str() {
+            "" | "no" => None,
+            "on-failure" => match policy.maximum_retry_count.unwrap_or(0) {
+                0 => Some("on-failure".to_string()),
+                retries => Some(format!("on-failure:{}", retries)),
+            },
+            other => Some(other.to_string()),
+        };
+    }
+
+    // Portas publicadas
+    for (container_port, bindings) in host_config.port_bindings.clone().unwrap_or_default() {
+        let (port, protocol) = container_port
+            .split_once('/')
+            .unwrap_or((container_port.as_str(), "tcp"));
+        let suffix = if protocol == "tcp" {
+            String::new()
+        } else {
+            format!("/{}", protocol)
+        };
+
+        for binding in bindings.unwrap_or_default() {
+            let host_ip = binding.host_ip.unwrap_or_default();
+            let host_port = binding.host_port.unwrap_or_default();
+            let mapping = match (host_ip.as_str(), host_port.as_str()) {
+                ("" | "0.0.0.0" | "::", "") => format!("{}{}", port, suffix),
+                ("" | "0.0.0.0" | "::", host_port) => {
+                    format!("{}:{}{}", host_port, port, suffix)
+                }
+                (host_ip, host_port) if host_ip.contains(':') => {
+                    format!("[{}]:{}:{}{}", host_ip, host_port, port, suffix)
+                }
+                (host_ip, host_port) => format!("{}:{}:{}{}", host_ip, host_port, port, suffix),
+            };
+            spec.ports.push(mapping);
+        }
+    }
+    spec.ports.sort();
+    spec.ports.dedup();
+
+    // Volumes: binds ficam como -v, mounts como --mount e tmpfs à parte
+    spec.volumes = host_config.binds.clone().unwrap_or_default();
+    for mount in host_config.mounts.clone().unwrap_or_default() {
+        let typ = mount.typ.map(|t| t.to_string()).unwrap_or_default();
+        let target = mount.target.unwrap_or_default();
+        match typ.as_str() {
+            "tmpfs" => spec.tmpfs.push(target),
+            "bind" | "volume" => {
+                let source = mount.source.unwrap_or_default();
+                let mode = if mount.read_only == Some(true) {
+                    ":ro"
+                } else {
+                    ""
+                };
+                if source.is_empty() {
+                    spec.mounts.push(format!("type=volume,target={}", target));
+                } else {
+                    spec.volumes.push(format!("{}:{}{}", source, target, mode));
+                }
+            }
+            _ => {
+                let mut parts = vec![format!("type={}", typ)];
+                if let Some(source) = mount.source.filter(|s| !s.is_empty()) {
+                    parts.push(format!("source={}", source));
+                }
+                parts.push(format!("target={}", target));
+                if mount.read_only == Some(true) {
+                    parts.push("readonly".to_string());
+                }
+                spec.mounts.push(parts.join(","));
+            }
+        }
+    }
+    for (path, options) in host_config.tmpfs.clone().unwrap_or_default() {
+        if options.is_empty() {
+            spec.tmpfs.push(path);
+        } else {
+            spec.tmpfs.push(format!("{}:{}", path, options));
+        }
+    }
+    spec.tmpfs.sort();
+
+    // Variáveis de ambiente que não vieram da imagem
+    let image_env = image_config.env.clone().unwrap_or_default();
+    spec.env = config
+        .env
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|var| !image_env.contains(var))
+        .map(|var| match var.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (var, String::new()),
+        })
+        .collect();
+
+    // Labels próprios do container (ignora os da imagem e os gerados pelo compose)
+    let image_labels = image_config.labels.clone().unwrap_or_default();
+    let mut labels: Vec<(String, String)> = config
+        .labels
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(key, value)| {
+            !key.starts_with("com.docker.compose.") && image_labels.get(key) != Some(value)
+        })
+        .collect();
+    labels.sort();
+    spec.labels = labels;
+
+    // Redes
+    let network_mode = host_config.network_mode.clone().unwrap_or_default();
+    if network_mode == "host" || network_mode == "none" || network_mode.starts_with("container:") {
+        spec.network_mode = Some(network_mode);
+    } else {
+        let mut networks: Vec<(String, Vec<String>)> = inspect
+            .network_settings
+            .as_ref()
+            .and_then(|settings| settings.networks.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(network, _)| network != "bridge")
+            .map(|(network, endpoint)| {
+                let aliases = endpoint
+                    .aliases
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|alias| *alias != short_id && *alias != name)
+                    .collect();
+                (network, aliases)
+            })
+            .collect();
+        networks.sort();
+        spec.networks = networks;
+    }
+
+    // Campos simples que só aparecem quando diferem da imagem
+    spec.hostname = config
+        .hostname
+        .clone()
+        .filter(|hostname| !hostname.is_empty() && !id.starts_with(hostname.as_str()));
+    spec.user = config
+        .user
+        .clone()
+        .filter(|user| !user.is_empty() && Some(user) != image_config.user.as_ref());
+    spec.working_dir = config
+        .working_dir
+        .clone()
+        .filter(|dir| !dir.is_empty() && Some(dir) != image_config.working_dir.as_ref());
+    spec.entrypoint = config
+        .entrypoint
+        .clone()
+        .filter(|entrypoint| Some(entrypoint) != image_config.entrypoint.as_ref());
+    spec.command = config
+        .cmd
+        .clone()
+        .filter(|cmd| !cmd.is_empty() && Some(cmd) != image_config.cmd.as_ref());
+
+    // Limites de recursos
+    let positive = |value: Option<i64>| value.filter(|v| *v > 0);
+    spec.memory = positive(host_config.memory);
+    spec.memory_swap = host_config.memory_swap.filter(|v| *v != 0);
+    spec.memory_reservation = positive(host_config.memory_reservation);
+    spec.cpus = positive(host_config.nano_cpus).map(|nano| nano as f64 / 1_000_000_000.0);
+    spec.cpu_shares = positive(host_config.cpu_shares);
+    spec.cpu_quota = positive(host_config.cpu_quota);
+    spec.cpu_period = positive(host_config.cpu_period);
+    spec.cpuset_cpus = host_config.cpuset_cpus.clone().filter(|c| !c.is_empty());
+    spec.pids_limit = positive(host_config.pids_limit);
+    // 64 MB é o tamanho padrão do /dev/shm
+    spec.shm_size = host_config
+        .shm_size
+        .filter(|size| *size > 0 && *size != 64 * 1024 * 1024);
+
+    spec.privileged = host_config.privileged.unwrap_or(false);
+    spec.cap_add = host_config.cap_add.clone().unwrap_or_default();
+    spec.cap_drop = host_config.cap_drop.clone().unwrap_or_default();
+    spec.extra_hosts = host_config.extra_hosts.clone().unwrap_or_default();
+    spec.dns = host_config.dns.clone().unwrap_or_default();
+    spec.name = name;
+
+    spec
+}
+
+// Monta o comando `docker run` com uma opção por linha
+fn render_docker_run(spec: &RunSpec) -> String {
+    let mut args: Vec<String> = vec!["docker run -d".to_string()];
+
+    if !spec.name.is_empty() {
+        args.push(format!("--name {}", shell_quote(&spec.name)));
+    }
+    if spec.auto_remove {
+        args.push("--rm".to_string());
+    }
+    if spec.interactive {
+        args.push("-i".to_string());
+    }
+    if spec.tty {
+        args.push("-t".to_string());
+    }
+    if let Some(restart) = &spec.restart {
+        args.push(format!("--restart {}", restart));
+    }
+    if let Some(hostname) = &spec.hostname {
+        args.push(format!("--hostname {}", shell_quote(hostname)));
+    }
+    if let Some(user) = &spec.user {
+        args.push(format!("--user {}", shell_quote(user)));
+    }
+    if let Some(dir) = &spec.working_dir {
+        args.push(format!("--workdir {}", shell_quote(dir)));
+    }
+    for port in &spec.ports {
+        args.push(format!("-p {}", shell_quote(port)));
+    }
+    for volume in &spec.volumes {
+        args.push(format!("-v {}", shell_quote(volume)));
+    }
+    for mount in &spec.mounts {
+        args.push(format!("--mount {}", shell_quote(mount)));
+    }
+    for tmpfs in &spec.tmpfs {
+        args.push(format!("--tmpfs {}", shell_quote(tmpfs)));
+    }
+    for (key, value) in &spec.env {
+        args.push(format!("-e {}", shell_quote(&format!("{}={}", key, value))));
+    }
+    if let Some(mode) = &spec.network_mode {
+        args.push(format!("--network {}", shell_quote(mode)));
+    }
+    for (network, aliases) in &spec.networks {
+        args.push(format!("--network {}", shell_quote(network)));
+        for alias in aliases {
+            args.push(format!("--network-alias {}", shell_quote(alias)));
+        }
+    }
+    for (key, value) in &spec.labels {
+        args.push(format!(
+            "--label {}",
+            shell_quote(&format!("{}={}", key, value))
+        ));
+    }
+    if let Some(memory) = spec.memory {
+        args.push(format!("--memory {}", format_size_flag(memory)));
+    }
+    if let Some(swap) = spec.memory_swap {
+        args.push(format!("--memory-swap {}", format_size_flag(swap)));
+    }
+    if let Some(reservation) = spec.memory_reservation {
+        args.push(format!(
+            "--memory-reservation {}",
+            format_size_flag(reservation)
+        ));
+    }
+    if let Some(cpus) = spec.cpus {
+        args.push(format!("--cpus {}", cpus));
+    }
+    if let Some(shares) = spec.cpu_shares {
+        args.push(format!("--cpu-shares {}", shares));
+    }
+    if let Some(quota) = spec.cpu_quota {
+        args.push(format!("--cpu-quota {}", quota));
+    }
+    if let Some(period) = spec.cpu_period {
+        args.push(format!("--cpu-period {}", period));
+    }
+    if let Some(cpuset) = &spec.cpuset_cpus {
+        args.push(format!("--cpuset-cpus {}", shell_quote(cpuset)));
+    }
+    if let Some(pids) = spec.pids_limit {
+        args.push(format!("--pids-limit {}", pids));
+    }
+    if let Some(shm) = spec.shm_size {
+        args.push(format!("--shm-size {}", format_size_flag(shm)));
+    }
+    if spec.privileged {
+        args.push("--privileged".to_string());
+    }
+    for cap in &spec.cap_add {
+        args.push(format!("--cap-add {}", cap));
+    }
+    for cap in &spec.cap_drop {
+        args.push(format!("--cap-drop {}", cap));
+    }
+    for host in &spec.extra_hosts {
+        args.push(format!("--add-host {}", shell_quote(host)));
+    }
+    for dns in &spec.dns {
+        args.push(format!("--dns {}", shell_quote(dns)));
+    }
+    if let Some(entrypoint) = &spec.entrypoint {
+        // --entrypoint aceita apenas o executável; os argumentos vão depois da imagem
+        let executable = entrypoint.first().cloned().unwrap_or_default();
+        args.push(format!("--entrypoint {}", shell_quote(&executable)));
+    }
+
+    let mut image_and_args = vec![shell_quote(&spec.image)];
+    if let Some(entrypoint) = &spec.entrypoint {
+        image_and_args.extend(entrypoint.iter().skip(1).map(|arg| shell_quote(arg)));
+    }
+    if let Some(command) = &spec.command {
+        image_and_args.extend(command.iter().map(|arg| shell_quote(arg)));
+    }
+    args.push(image_and_args.join(" "));
+
+    args.join(" \\\n  ")
+}
+
+// Monta o bloco YAML de um serviço do compose
+fn render_compose(spec: &RunSpec) -> String {
+    let service = compose_service_name(&spec.name);
+    let mut lines = vec!["services:".to_string(), format!("  {}:", service)];
+    let mut push = |line: String| lines.push(format!("    {}", line));
+
+    push(format!("image: {}", yaml_quote(&spec.image)));
+    if !spec.name.is_empty() {
+        push(format!("container_name: {}", yaml_quote(&spec.name)));
+    }
+    if let Some(restart) = &spec.restart {
+        push(format!("restart: {}", yaml_quote(restart)));
+    }
+    if let Some(hostname) = &spec.hostname {
+        push(format!("hostname: {}", yaml_quote(hostname)));
+    }
+    if let Some(user) = &spec.user {
+        push(format!("user: {}", yaml_quote(user)));
+    }
+    if let Some(dir) = &spec.working_dir {
+        push(format!("working_dir: {}", yaml_quote(dir)));
+    }
+    if spec.interactive {
+        push("stdin_open: true".to_string());
+    }
+    if spec.tty {
+        push("tty: true".to_string());
+    }
+    if let Some(entrypoint) = &spec.entrypoint {
+        push(format!("entrypoint: {}", yaml_list(entrypoint)));
+    }
+    if let Some(command) = &spec.command {
+        push(format!("command: {}", yaml_list(command)));
+    }
+    if !spec.env.is_empty() {
+        push("environment:".to_string());
+        for (key, value) in &spec.env {
+            push(format!("  {}: {}", yaml_key(key), yaml_quote(value)));
+        }
+    }
+    if !spec.ports.is_empty() {
+        push("ports:".to_string());
+        for port in &spec.ports {
+            push(format!("  - {}", yaml_quote(port)));
+        }
+    }
+    if !spec.volumes.is_empty() {
+        push("volumes:".to_string());
+        for volume in &spec.volumes {
+            push(format!("  - {}", yaml_quote(volume)));
+        }
+    }
+    if !spec.tmpfs.is_empty() {
+        push("tmpfs:".to_string());
+        for tmpfs in &spec.tmpfs {
+            push(format!("  - {}", yaml_quote(tmpfs)));
+        }
+    }
+    if let Some(mode) = &spec.network_mode {
+        push(format!("network_mode: {}", yaml_quote(mode)));
+    }
+    if !spec.networks.is_empty() {
+        push("networks:".to_string());
+        for (network, aliases) in &spec.networks {
+            if aliases.is_empty() {
+                push(format!("  {}: {{}}", yaml_key(network)));
+            } else {
+                push(format!("  {}:", yaml_key(network)));
+                push(format!("    aliases: {}", yaml_list(aliases)));
+            }
+        }
+    }
+    if !spec.labels.is_empty() {
+        push("labels:".to_string());
+        for (key, value) in &spec.labels {
+            push(format!("  {}: {}", yaml_key(key), yaml_quote(value)));
+        }
+    }
+    if let Some(memory) = spec.memory {
+        push(format!("mem_limit: {}", format_size_flag(memory)));
+    }
+    if let Some(swap) = spec.memory_swap {
+        push(format!("memswap_limit: {}", format_size_flag(swap)));
+    }
+    if let Some(reservation) = spec.memory_reservation {
+        push(format!(
+            "mem_reservation: {}",
+            format_size_flag(reservation)
+        ));
+    }
+    if let Some(cpus) = spec.cpus {
+        push(format!("cpus: {}", cpus));
+    }
+    if let Some(shares) = spec.cpu_shares {
+        push(format!("cpu_shares: {}", shares));
+    }
+    if let Some(quota) = spec.cpu_quota {
+        push(format!("cpu_quota: {}", quota));
+    }
+    if let Some(period) = spec.cpu_period {
+        push(format!("cpu_period: {}", period));
+    }
+    if let Some(cpuset) = &spec.cpuset_cpus {
+        push(format!("cpuset: {}", yaml_quote(cpuset)));
+    }
+    if let Some(pids) = spec.pids_limit {
+        push(format!("pids_limit: {}", pids));
+    }
+    if let Some(shm) = spec.shm_size {
+        push(format!("shm_size: {}", format_size_flag(shm)));
+    }
+    if spec.privileged {
+        push("privileged: true".to_string());
+    }
+    if !spec.cap_add.is_empty() {
+        push(format!("cap_add: {}", yaml_list(&spec.cap_add)));
+    }
+    if !spec.cap_drop.is_empty() {
+        push(format!("cap_drop: {}", yaml_list(&spec.cap_drop)));
+    }
+    if !spec.extra_hosts.is_empty() {
+        push(format!("extra_hosts: {}", yaml_list(&spec.extra_hosts)));
+    }
+    if !spec.dns.is_empty() {
+        push(format!("dns: {}", yaml_list(&spec.dns)));
+    }
+
+    // Redes e volumes nomeados já existem; o compose deve apenas referenciá-los
+    if !spec.networks.is_empty() {
+        lines.push("networks:".to_string());
+        for (network, _) in &spec.networks {
+            lines.push(format!("  {}:", yaml_key(network)));
+            lines.push("    external: true".to_string());
+        }
+    }
+    let named_volumes: Vec<&str> = spec
+        .volumes
+        .iter()
+        .filter_map(|volume| volume.split(':').next())
+        .filter(|source| is_named_volume(source))
+        .collect();
+    if !named_volumes.is_empty() {
+        lines.push("volumes:".to_string());
+        for volume in named_volumes {
+            lines.push(format!("  {}:", yaml_key(volume)));
+            lines.push("    external: true".to_string());
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+// Volume nomeado (e não caminho do host) na origem de um -v
+pub fn is_named_volume(source: &str) -> bool {
+    !source.is_empty()
+        && !source.starts_with('/')
+        && !source.starts_with('.')
+        && !source.starts_with('~')
+        && !source.contains('\\')
+}
+
+// Nome de serviço aceito pelo compose
+fn compose_service_name(name: &str) -> String {
+    let service: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+
+    if service.is_empty() {
+        "app".to_string()
+    } else {
+        service
+    }
+}
+
+// Formata bytes com o maior sufixo exato aceito pelo Docker (k, m, g)
+fn format_size_flag(bytes: i64) -> String {
+    const KB: i64 = 1024;
+    if bytes > 0 && bytes % (KB * KB * KB) == 0 {
+        format!("{}g", bytes / (KB * KB * KB))
+    } else if bytes > 0 && bytes % (KB * KB) == 0 {
+        format!("{}m", bytes / (KB * KB))
+    } else if bytes > 0 && bytes % KB == 0 {
+        format!("{}k", bytes / KB)
+    } else {
+        bytes.to_string()
+    }
+}
+
+// Aspas simples para o shell apenas quando necessário
+fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value.chars().all(|c| {
+            c.is_ascii_alphanumeric()
+                || matches!(c, '_' | '-' | '.' | '/' | ':' | '=' | '@' | '%' | '+' | ',')
+        });
+
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+// String YAML entre aspas duplas
+fn yaml_quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+// Chave YAML, com aspas apenas se tiver caracteres especiais
+fn yaml_key(key: &str) -> String {
+    let safe = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+
+    if safe {
+        key.to_string()
+    } else {
+        yaml_quote(key)
+    }
+}
+
+// Lista YAML no formato de fluxo: ["a", "b"]
+fn yaml_list(items: &[String]) -> String {
+    let quoted: Vec<String> = items.iter().map(|item| yaml_quote(item)).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+// Converte um `docker run ...` colado pelo usuário em uma requisição de criação
+pub fn parse_docker_run(command_line: &str) -> Result<DockerRunImport> {
+    let mut tokens = split_shell_words(command_line)?;
+
+    // Remove o prefixo "docker run" / "docker container run" (com ou sem "$ ")
+    if tokens.first().map(String::as_str) == Some("$") {
+        tokens.remove(0);
+    }
+    if tokens.first().map(String::as_str) == Some("docker") {
+        tokens.remove(0);
+        if tokens.first().map(String::as_str) == Some("container") {
+            tokens.remove(0);
+        }
+        match tokens.first().map(String::as_str) {
+            Some("run") => {
+                tokens.remove(0);
+            }
+            Some("create") => {
+                tokens.remove(0);
+            }
+            _ => return Err(anyhow::anyhow!("O comando deve ser um `docker run`")),
+        }
+    } else if tokens.first().map(String::as_str) == Some("run") {
+        tokens.remove(0);
+    }
+
+    let mut request = CreateContainerRequest {
+        name: String::new(),
+        image: String::new(),
+        ports: Vec::new(),
+        volumes: Vec::new(),
+        environment: Vec::new(),
+        command: None,
+        restart_policy: "no".to_string(),
+        network: None,
+        auto_remove: None,
+    };
+    let mut warnings = Vec::new();
+
+    let mut index = 0;
+    while index < tokens.len() {
+        let token = tokens[index].clone();
+        index += 1;
+
+        if token == "--" {
+            continue;
+        }
+        if !token.starts_with('-') || token == "-" {
+            // Primeiro argumento posicional é a imagem; o resto é o comando
+            request.image = token;
+            let args: Vec<String> = tokens[index..].to_vec();
+            if !args.is_empty() {
+                if args.iter().any(|arg| arg.chars().any(char::is_whitespace)) {
+                    warnings.push(
+                        "Argumentos do comando com espaços serão separados por palavra".to_string(),
+                    );
+                }
+                request.command = Some(args.join(" "));
+            }
+            break;
+        }
+
+        // Normaliza a opção para (nome longo, valor embutido)
+        let (flag, inline_value) = if let Some(long) = token.strip_prefix("--") {
+            match long.split_once('=') {
+                Some((name, value)) => (format!("--{}", name), Some(value.to_string())),
+                None => (token.clone(), None),
+            }
+        } else {
+            // Opções curtas podem vir agrupadas (-dit) ou com o valor colado (-p80:80)
+            let chars: Vec<char> = token[1..].chars().collect();
+            let mut resolved = None;
+            for (position, c) in chars.iter().enumerate() {
+                if let Some((_, long)) = RUN_SHORT_VALUE_FLAGS.iter().find(|(s, _)| s == c) {
+                    let rest: String = chars[position + 1..].iter().collect();
+                    let rest = rest.strip_prefix('=').map(str::to_string).unwrap_or(rest);
+                    resolved = Some((
+                        long.to_string(),
+                        if rest.is_empty() { None } else { Some(rest) },
+                    ));
+                    break;
+                }
+                match c {
+                    'd' | 'i' | 't' | 'q' => {}
+                    'P' => {
+                        warnings.push("Opção -P (publicar todas as portas) ignorada".to_string())
+                    }
+                    other => warnings.push(format!("Opção não suportada ignorada: -{}", other)),
+                }
+            }
+            match resolved {
+                Some(resolved) => resolved,
+                None => continue,
+            }
+        };
+
+        let takes_value = RUN_VALUE_FLAGS.contains(&flag.as_str());
+        let value = if takes_value {
+            match inline_value {
+                Some(value) => Some(value),
+                None if index < tokens.len() => {
+                    index += 1;
+                    Some(tokens[index - 1].clone())
+                }
+                None => return Err(anyhow::anyhow!("A opção {} precisa de um valor", flag)),
+            }
+        } else {
+            inline_value
+        };
+
+        match (flag.as_str(), value) {
+            ("--name", Some(name)) => request.name = name,
+            ("--publish", Some(spec)) => match parse_port_spec(&spec) {
+                Ok(port) => request.ports.push(port),
+                Err(e) => warnings.push(e.to_string()),
+            },
+            ("--volume", Some(spec)) => match parse_volume_spec(&spec) {
+                Some(volume) => request.volumes.push(volume),
+                None => warnings.push(format!(
+                    "Volume anônimo ignorado (informe origem e destino): {}",
+                    spec
+                )),
+            },
+            ("--env", Some(var)) => match var.split_once('=') {
+                Some((key, value)) => request.environment.push(EnvVar {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                }),
+                // A CLI copiaria o valor do ambiente de quem roda o comando; o do app não
+                // serve (e poderia vazar segredos do processo), então só avisa
+                None => warnings.push(format!(
+                    "Variável {} sem valor ignorada (a CLI usaria o valor do ambiente local); informe o valor manualmente",
+                    var
+                )),
+            },
+            ("--env-file", Some(path)) => {
+                match std::fs::read_to_string(&path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|content| parse_env_file(&content))
+                {
+                    Ok(vars) => request.environment.extend(vars),
+                    Err(e) => warnings.push(format!(
+                        "Não foi possível ler o arquivo de ambiente {}: {}",
+                        path, e
+                    )),
+                }
+            }
+            ("--restart", Some(policy)) => {
+                let (name, retries) = policy.split_once(':').unwrap_or((policy.as_str(), ""));
+                if !matches!(name, "no" | "always" | "unless-stopped" | "on-failure") {
+                    warnings.push(format!("Política de restart desconhecida: {}", policy));
+                } else {
+                    if !retries.is_empty() {
+                        warnings.push(format!(
+                            "Número máximo de tentativas ({}) ignorado; será usado o padrão",
+                            retries
+                        ));
+                    }
+                    request.restart_policy = name.to_string();
+                }
+            }
+            ("--network" | "--net", Some(network)) => {
+                if request.network.is_some() {
+                    warnings.push(format!("Rede adicional ignorada: {}", network));
+                } else {
+                    request.network = Some(network);
+                }
+            }
+            ("--rm", _) => request.auto_remove = Some(true),
+            ("--detach" | "--interactive" | "--tty", _) => {}
+            (flag, _) => warnings.push(format!("Opção não suportada ignorada: {}", flag)),
+        }
+    }
+
+    if request.image.is_empty() {
+        return Err(anyhow::anyhow!("Imagem não encontrada no comando"));
+    }
+
+    if request.auto_remove == Some(true) && request.restart_policy != "no" {
+        warnings.push("--rm não pode ser combinado com política de restart".to_string());
+    }
+
+    Ok(DockerRunImport { request, warnings })
+}
+
+// Converte "-p" (ex.: "8080:80", "127.0.0.1:8080:80/udp", "8000-8001:8000-8001", "80")
+fn parse_port_spec(spec: &str) -> Result<PortMapping> {
+    let invalid = || anyhow::anyhow!("Mapeamento de porta inválido: {}", spec);
+
+    let (addresses, protocol) = match spec.rsplit_once('/') {
+        Some((addresses, protocol)) => (addresses, protocol.to_lowercase()),
+        None => (spec, "tcp".to_string()),
+    };
+
+    // Separa IP (inclusive IPv6 entre colchetes), porta do host e porta do container
+    let (host_ip, rest) = if let Some(stripped) = addresses.strip_prefix('[') {
+        let (ip, rest) = stripped.split_once("]:").ok_or_else(invalid)?;
+        (Some(ip.to_string()), rest.to_string())
+    } else {
+        let parts: Vec<&str> = addresses.split(':').collect();
+        if parts.len() == 3 {
+            (
+                Some(parts[0].to_string()),
+                format!("{}:{}", parts[1], parts[2]),
+            )
+        } else {
+            (None, addresses.to_string())
+        }
+    };
+
+    let (host, container) = match rest.split_once(':') {
+        Some((host, container)) => (host.to_string(), container.to_string()),
+        None => (String::new(), rest.clone()),
+    };
+
+    // Porta do host vazia: o Docker escolhe uma porta livre
+    let (container_port, container_port_end) = parse_port_range(&container).ok_or_else(invalid)?;
+    let (host_port, host_port_end) = if host.is_empty() {
+        (None, None)
+    } else {
+        let (start, end) = parse_port_range(&host).ok_or_else(invalid)?;
+        (Some(start), end)
+    };
+
+    // Intervalo no container exige intervalo do mesmo tamanho no host
+    if let (Some(container_end), Some(host_start)) = (container_port_end, host_port) {
+        let host_end = host_port_end.unwrap_or(host_start);
+        if container_end - container_port != host_end - host_start {
+            return Err(invalid());
+        }
+    }
+
+    Ok(PortMapping {
+        host_ip: host_ip.filter(|ip| !ip.is_empty()),
+        host_port,
+        host_port_end,
+        container_port,
+        container_port_end,
+        protocol,
+    })
+}
+
+// Porta única ("80") ou intervalo ("8000-8010")
+fn parse_port_range(value: &str) -> Option<(u16, Option<u16>)> {
+    match value.split_once('-') {
+        Some((start, end)) => {
+            let start: u16 = start.trim().parse().ok()?;
+            let end: u16 = end.trim().parse().ok()?;
+            if start > end {
+                return None;
+            }
+            Some((start, Some(end)))
+        }
+        None => Some((value.trim().parse().ok()?, None)),
+    }
+}
+
+// Converte "-v origem:destino[:opções]"; volumes anônimos não são suportados
+fn parse_volume_spec(spec: &str) -> Option<VolumeMapping> {
+    let parts: Vec<&str> = spec.split(':').collect();
+    let (host_path, container_path, options) = match parts.as_slice() {
+        [host, container] => (*host, *container, ""),
+        [host, container, options] => (*host, *container, *options),
+        _ => return None,
+    };
+
+    if host_path.is_empty() || container_path.is_empty() {
+        return None;
+    }
+
+    Some(VolumeMapping {
+        host_path: host_path.to_string(),
+        container_path: container_path.to_string(),
+        read_only: options.split(',').any(|option| option == "ro"),
+    })
+}
+
+// Divide uma linha de comando como o shell (aspas, escapes e quebras com "\")
+fn split_shell_words(input: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(escaped) => {
+                    current.push(escaped);
+                    in_word = true;
+                }
+                None => {}
+            },
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err(anyhow::anyhow!("Aspas simples não fechadas")),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(escaped @ ('"' | '\\' | '$' | '`')) => current.push(escaped),
+                            Some('\n') => {}
+                            Some(other) => {
+                                current.push('\\');
+                                current.push(other);
+                            }
+                            None => return Err(anyhow::anyhow!("Aspas duplas não fechadas")),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err(anyhow::anyhow!("Aspas duplas não fechadas")),
+                    }
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if in_word {
+        words.push(current);
+    }
+
+    Ok(words)
+}
@@ -0,0 +1,278 @@
+// Leitura de arquivos .env e ocultação de variáveis sensíveis
+use crate::docker::EnvVar;
+use anyhow::Result;
+use std::collections::HashMap;
+
+// Valor exibido no lugar de segredos
+pub const SECRET_MASK: &str = "********";
+
+// Trechos de nome que indicam uma variável sensível
+const SECRET_KEY_MARKERS: &[&str] = &[
+    "PASSWORD",
+    "PASSWD",
+    "TOKEN",
+    "SECRET",
+    "KEY",
+    "CREDENTIAL",
+    "PRIVATE",
+];
+
+// Indica se o nome da variável parece guardar um segredo
+pub fn is_secret_key(key: &str) -> bool {
+    let key = key.to_uppercase();
+    SECRET_KEY_MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+// Substitui o valor de variáveis sensíveis pela máscara
+pub fn mask_env_vars(vars: &mut [EnvVar]) {
+    for var in vars.iter_mut() {
+        if is_secret_key(&var.key) && !var.value.is_empty() {
+            var.value = SECRET_MASK.to_string();
+        }
+    }
+}
+
+// Mesmo que `mask_env_vars`, para pares (chave, valor)
+pub fn mask_env_pairs(pairs: &mut [(String, String)]) {
+    for (key, value) in pairs.iter_mut() {
+        if is_secret_key(key) && !value.is_empty() {
+            *value = SECRET_MASK.to_string();
+        }
+    }
+}
+
+// Mascara atribuições CHAVE=valor com chave sensível dentro de um texto livre, como as
+// linhas do histórico de uma imagem ("ENV DB_PASSWORD=...", "|1 TOKEN=... /bin/sh -c ...")
+pub fn mask_secret_assignments(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut position = 0;
+
+    while position < text.len() {
+        let rest = &text[position..];
+        let at_word_start = position == 0 || text[..position].ends_with(char::is_whitespace);
+
+        if at_word_start {
+            let key_len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            let key = &rest[..key_len];
+
+            if key_len > 0 && rest[key_len..].starts_with('=') && is_secret_key(key) {
+                // Valor vai até o próximo espaço ou, entre aspas, até a aspa de fechamento
+                let value = &rest[key_len + 1..];
+                let value_len = match value.chars().next() {
+                    Some(quote @ ('"' | '\'')) => value[1..]
+                        .find(quote)
+                        .map(|end| end + 2)
+                        .unwrap_or(value.len()),
+                    _ => value.find(char::is_whitespace).unwrap_or(value.len()),
+                };
+
+                result.push_str(key);
+                result.push('=');
+                if value_len > 0 {
+                    result.push_str(SECRET_MASK);
+                }
+                position += key_len + 1 + value_len;
+                continue;
+            }
+        }
+
+        let c = rest.chars().next().unwrap_or_default();
+        result.push(c);
+        position += c.len_utf8();
+    }
+
+    result
+}
+
+// Interpreta um arquivo .env: comentários, aspas, prefixo `export` e interpolação
+// (${VAR}, ${VAR:-padrão} e $VAR), usando só variáveis definidas antes no próprio arquivo
+pub fn parse_env_file(content: &str) -> Result<Vec<EnvVar>> {
+    let mut vars: Vec<EnvVar> = Vec::new();
+    let mut known: HashMap<String, String> = HashMap::new();
+    let mut lines = content.lines().enumerate();
+
+    while let Some((index, raw_line)) = lines.next() {
+        let line_number = index + 1;
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line
+            .strip_prefix("export ")
+            .map(str::trim_start)
+            .unwrap_or(line);
+        let (key, raw_value) = line.split_once('=').ok_or_else(|| {
+            anyhow::anyhow!("Linha {} inválida (esperado CHAVE=valor)", line_number)
+        })?;
+
+        let key = key.trim();
+        if !is_valid_env_key(key) {
+            return Err(anyhow::anyhow!(
+                "Linha {}: nome de variável inválido: {}",
+                line_number,
+                key
+            ));
+        }
+
+        let raw_value = raw_value.trim_start();
+        let value = match raw_value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                // Valores entre aspas podem ocupar várias linhas
+                let mut quoted = raw_value[1..].to_string();
+                while find_closing_quote(&quoted, quote).is_none() {
+                    match lines.next() {
+                        Some((_, next)) => {
+                            quoted.push('\n');
+                            quoted.push_str(next);
+                        }
+                        None => {
+                            return Err(anyhow::anyhow!(
+                                "Linha {}: aspas não fechadas",
+                                line_number
+                            ))
+                        }
+                    }
+                }
+
+                let end = find_closing_quote(&quoted, quote).unwrap_or(quoted.len());
+                let inner = &quoted[..end];
+                if quote == '\'' {
+                    inner.to_string()
+                } else {
+                    interpolate(&unescape_double_quoted(inner), &known)
+                }
+            }
+            _ => {
+                // Sem aspas, " #" inicia um comentário
+                let value = match raw_value.find(" #") {
+                    Some(position) => &raw_value[..position],
+                    None => raw_value,
+                };
+                interpolate(value.trim_end(), &known)
+            }
+        };
+
+        known.insert(key.to_string(), value.clone());
+        match vars.iter_mut().find(|var| var.key == key) {
+            Some(existing) => existing.value = value,
+            None => vars.push(EnvVar {
+                key: key.to_string(),
+                value,
+            }),
+        }
+    }
+
+    Ok(vars)
+}
+
+fn is_valid_env_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-')
+}
+
+// Posição da aspa de fechamento, ignorando as escapadas em aspas duplas
+fn find_closing_quote(text: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (position, c) in text.char_indices() {
+        match c {
+            '\\' if quote == '"' && !escaped => escaped = true,
+            c if c == quote && !escaped => return Some(position),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+fn unescape_double_quoted(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            // Mantém "\$" para não ser interpolado
+            Some('$') => result.push_str("\\$"),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+// Expande ${VAR}, ${VAR:-padrão}, ${VAR-padrão} e $VAR. O ambiente do processo do app não
+// é consultado: o resultado seria diferente em cada máquina e poderia expor segredos dela;
+// nomes desconhecidos ficam vazios (ou com o padrão)
+fn interpolate(text: &str, known: &HashMap<String, String>) -> String {
+    let lookup = |name: &str| known.get(name).cloned();
+
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'$') => {
+                chars.next();
+                result.push('$');
+            }
+            '$' if chars.peek() == Some(&'{') => {
+                chars.next();
+                let mut expression = String::new();
+                let mut closed = false;
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        closed = true;
+                        break;
+                    }
+                    expression.push(c);
+                }
+                if !closed {
+                    result.push_str("${");
+                    result.push_str(&expression);
+                    continue;
+                }
+
+                let value = if let Some((name, default)) = expression.split_once(":-") {
+                    lookup(name)
+                        .filter(|value| !value.is_empty())
+                        .unwrap_or_else(|| default.to_string())
+                } else if let Some((name, default)) = expression.split_once('-') {
+                    lookup(name).unwrap_or_else(|| default.to_string())
+                } else {
+                    lookup(&expression).unwrap_or_default()
+                };
+                result.push_str(&value);
+            }
+            '$' if chars
+                .peek()
+                .is_some_and(|c| c.is_ascii_alphabetic() || *c == '_') =>
+            {
+                let mut name = String::new();
+                while let Some(c) = chars.peek() {
+                    if c.is_ascii_alphanumeric() || *c == '_' {
+                        name.push(*c);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                result.push_str(&lookup(&name).unwrap_or_default());
+            }
+            c => result.push(c),
+        }
+    }
+
+    result
+}
@@ -0,0 +1,76 @@
+// Detecção de portas ocupadas no host (fora do Docker)
+use std::collections::HashSet;
+use std::io::ErrorKind;
+use std::net::{TcpListener, UdpSocket};
+
+// Estado LISTEN na tabela /proc/net/tcp
+const TCP_LISTEN_STATE: &str = "0A";
+
+// Portas com socket aberto no host, lidas de /proc/net (somente Linux)
+pub fn bound_ports(protocol: &str) -> HashSet<u16> {
+    let tables: &[&str] = match protocol {
+        "tcp" => &["/proc/net/tcp", "/proc/net/tcp6"],
+        "udp" => &["/proc/net/udp", "/proc/net/udp6"],
+        _ => &[],
+    };
+
+    let mut ports = HashSet::new();
+    for table in tables {
+        if let Ok(content) = std::fs::read_to_string(table) {
+            ports.extend(parse_proc_net_table(&content, protocol == "tcp"));
+        }
+    }
+
+    ports
+}
+
+// Verifica se a porta está ocupada: /proc/net quando disponível e tentativa de bind
+pub fn is_port_in_use(port: u16, protocol: &str, bound: &HashSet<u16>) -> bool {
+    if bound.contains(&port) {
+        return true;
+    }
+
+    let result = match protocol {
+        "tcp" => TcpListener::bind(("0.0.0.0", port)).map(|_| ()),
+        "udp" => UdpSocket::bind(("0.0.0.0", port)).map(|_| ()),
+        // SCTP não pode ser testado com a biblioteca padrão
+        _ => return false,
+    };
+
+    // Sem permissão (portas < 1024) não significa que a porta esteja em uso
+    matches!(result, Err(e) if e.kind() == ErrorKind::AddrInUse)
+}
+
+// Porta livre escolhida pelo sistema operacional
+pub fn os_assigned_port(protocol: &str) -> Option<u16> {
+    match protocol {
+        "udp" => UdpSocket::bind(("0.0.0.0", 0))
+            .and_then(|socket| socket.local_addr())
+            .map(|addr| addr.port())
+            .ok(),
+        _ => TcpListener::bind(("0.0.0.0", 0))
+            .and_then(|listener| listener.local_addr())
+            .map(|addr| addr.port())
+            .ok(),
+    }
+}
+
+// Linhas no formato "sl local_address rem_address st ..." com endereço "IP:PORTA" em hexadecimal
+fn parse_proc_net_table(content: &str, listening_only: bool) -> Vec<u16> {
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let local_address = fields.get(1)?;
+            let state = fields.get(3)?;
+
+            if listening_only && *state != TCP_LISTEN_STATE {
+                return None;
+            }
+
+            let (_, port) = local_address.rsplit_once(':')?;
+            u16::from_str_radix(port, 16).ok().filter(|port| *port != 0)
+        })
+        .collect()
+}
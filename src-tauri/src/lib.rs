@@ -1,16 +1,45 @@
 use crate::docker::{
-    ContainerInfo, CreateContainerRequest, DockerInfo, DockerManager, DockerSystemUsage, ImageInfo,
-    NetworkInfo, VolumeInfo,
+    BuildImageRequest, BuildImageResult, BulkActionRequest, BulkActionResult,
+    CommitContainerRequest, ContainerChanges, ContainerFileEntry, ContainerHealth, ContainerInfo,
+    ContainerTop, CreateContainerRequest, CreateContainerResult, DockerInfo, DockerManager,
+    DockerSystemUsage, EnvVar, ImageDetails, ImageInfo, ImageUpdateReport, LoadImagesResult,
+    NetworkInfo, PortCheckResult, PortMapping, PruneImagesPreview, PruneImagesRequest,
+    PruneImagesResult, RecreateContainerRequest, RecreateContainerResult, RegistryCredentials,
+    RemoveImageResult, RunTaskRequest, RunTaskResult, UpdateContainerRequest, VolumeInfo,
 };
+use crate::docker_run::{parse_docker_run, ContainerRunSnippets, DockerRunImport};
 
-use tauri::State;
+use crate::environment::parse_env_file;
+use crate::registry_auth::{with_credential_store, RegistryLogin};
+use crate::registry_client::{RegistryClient, RegistryConnection, RegistryManifest, RegistryTag};
+use crate::templates::{
+    mask_template_secrets, render_template, ContainerTemplate, TemplateImportResult, TemplateStore,
+};
+
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Manager, State};
 use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
 
+mod build_context;
 mod docker;
+mod docker_run;
+mod environment;
+mod host_ports;
+mod registry_auth;
+mod registry_client;
+mod templates;
 
 // Global Docker Manager para manter cache entre chamadas
 type DockerManagerState = Mutex<Option<DockerManager>>;
 
+// Operações longas (save/load de imagens) que podem ser canceladas pelo id
+type CancellationState = Mutex<HashMap<String, CancellationToken>>;
+
+// Tarefa que repassa os eventos de saúde dos containers ao frontend
+type HealthWatcherState = Mutex<Option<tauri::async_runtime::JoinHandle<()>>>;
+
 async fn get_docker_manager(
     state: &State<'_, DockerManagerState>,
 ) -> Result<DockerManager, String> {
@@ -149,9 +178,10 @@ async fn docker_start_container(
 async fn docker_stop_container(
     state: State<'_, DockerManagerState>,
     container_id: String,
+    timeout: Option<i32>,
 ) -> Result<String, String> {
     let manager = get_docker_manager(&state).await?;
-    match manager.stop_container(&container_id).await {
+    match manager.stop_container(&container_id, timeout).await {
         Ok(_) => {
             set_docker_manager(&state, manager).await;
             Ok("Container stopped successfully".to_string())
@@ -203,9 +233,10 @@ async fn docker_unpause_container(
 async fn docker_restart_container(
     state: State<'_, DockerManagerState>,
     container_id: String,
+    timeout: Option<i32>,
 ) -> Result<String, String> {
     let manager = get_docker_manager(&state).await?;
-    match manager.restart_container(&container_id).await {
+    match manager.restart_container(&container_id, timeout).await {
         Ok(_) => {
             set_docker_manager(&state, manager).await;
             Ok("Container restarted successfully".to_string())
@@ -221,9 +252,18 @@ async fn docker_restart_container(
 async fn docker_remove_container(
     state: State<'_, DockerManagerState>,
     container_id: String,
+    force: Option<bool>,
+    remove_volumes: Option<bool>,
 ) -> Result<String, String> {
     let manager = get_docker_manager(&state).await?;
-    match manager.remove_container(&container_id).await {
+    match manager
+        .remove_container(
+            &container_id,
+            force.unwrap_or(false),
+            remove_volumes.unwrap_or(false),
+        )
+        .await
+    {
         Ok(_) => {
             set_docker_manager(&state, manager).await;
             Ok("Container removed successfully".to_string())
@@ -235,16 +275,54 @@ async fn docker_remove_container(
     }
 }
 
+#[tauri::command]
+async fn docker_rename_container(
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    new_name: String,
+) -> Result<String, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.rename_container(&container_id, &new_name).await {
+        Ok(_) => {
+            set_docker_manager(&state, manager).await;
+            Ok("Container renamed successfully".to_string())
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_kill_container(
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    signal: String,
+) -> Result<String, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.kill_container(&container_id, &signal).await {
+        Ok(_) => {
+            set_docker_manager(&state, manager).await;
+            Ok("Signal sent successfully".to_string())
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
 #[tauri::command]
 async fn docker_create_container(
     state: State<'_, DockerManagerState>,
     request: CreateContainerRequest,
-) -> Result<String, String> {
+) -> Result<CreateContainerResult, String> {
     let manager = get_docker_manager(&state).await?;
     match manager.create_container(request).await {
-        Ok(container_id) => {
+        Ok(result) => {
             set_docker_manager(&state, manager).await;
-            Ok(container_id)
+            Ok(result)
         }
         Err(e) => {
             set_docker_manager(&state, manager).await;
@@ -274,12 +352,17 @@ async fn docker_list_images(
 async fn docker_remove_image(
     state: State<'_, DockerManagerState>,
     image_id: String,
-) -> Result<String, String> {
+    force: Option<bool>,
+    noprune: Option<bool>,
+) -> Result<RemoveImageResult, String> {
     let manager = get_docker_manager(&state).await?;
-    match manager.remove_image(&image_id).await {
-        Ok(_) => {
+    match manager
+        .remove_image(&image_id, force.unwrap_or(false), noprune.unwrap_or(false))
+        .await
+    {
+        Ok(result) => {
             set_docker_manager(&state, manager).await;
-            Ok("Image removed successfully".to_string())
+            Ok(result)
         }
         Err(e) => {
             set_docker_manager(&state, manager).await;
@@ -294,7 +377,7 @@ async fn docker_pull_image(
     image_name: String,
 ) -> Result<String, String> {
     let manager = get_docker_manager(&state).await?;
-    match manager.pull_image(&image_name).await {
+    match manager.pull_image(&image_name, None).await {
         Ok(_) => {
             set_docker_manager(&state, manager).await;
             Ok("Image pulled successfully".to_string())
@@ -432,11 +515,852 @@ async fn docker_get_container_stats_for_graph(
     }
 }
 
+#[tauri::command]
+async fn docker_list_container_files(
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    path: String,
+) -> Result<Vec<ContainerFileEntry>, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.list_container_files(&container_id, &path).await {
+        Ok(entries) => {
+            set_docker_manager(&state, manager).await;
+            Ok(entries)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_download_from_container(
+    app: AppHandle,
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    container_path: String,
+    host_path: String,
+) -> Result<u64, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager
+        .download_from_container(&container_id, &container_path, &host_path, |progress| {
+            let _ = app.emit("container-transfer-progress", progress);
+        })
+        .await
+    {
+        Ok(bytes) => {
+            set_docker_manager(&state, manager).await;
+            Ok(bytes)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_upload_to_container(
+    app: AppHandle,
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    host_paths: Vec<String>,
+    container_path: String,
+) -> Result<u64, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager
+        .upload_to_container(
+            &container_id,
+            &host_paths,
+            &container_path,
+            move |progress| {
+                let _ = app.emit("container-transfer-progress", progress);
+            },
+        )
+        .await
+    {
+        Ok(bytes) => {
+            set_docker_manager(&state, manager).await;
+            Ok(bytes)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_container_top(
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    ps_args: Option<String>,
+) -> Result<ContainerTop, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.container_top(&container_id, ps_args).await {
+        Ok(top) => {
+            set_docker_manager(&state, manager).await;
+            Ok(top)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_signal_container_process(
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    container_pid: u32,
+    signal: String,
+) -> Result<String, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager
+        .signal_container_process(&container_id, container_pid, &signal)
+        .await
+    {
+        Ok(_) => {
+            set_docker_manager(&state, manager).await;
+            Ok("Signal sent successfully".to_string())
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_container_changes(
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    with_sizes: Option<bool>,
+) -> Result<ContainerChanges, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager
+        .container_changes(&container_id, with_sizes.unwrap_or(false))
+        .await
+    {
+        Ok(changes) => {
+            set_docker_manager(&state, manager).await;
+            Ok(changes)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_commit_container(
+    state: State<'_, DockerManagerState>,
+    request: CommitContainerRequest,
+) -> Result<String, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.commit_container(request).await {
+        Ok(image_id) => {
+            set_docker_manager(&state, manager).await;
+            Ok(image_id)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_update_container(
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    request: UpdateContainerRequest,
+) -> Result<bollard::models::HostConfig, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.update_container(&container_id, request).await {
+        Ok(resources) => {
+            set_docker_manager(&state, manager).await;
+            Ok(resources)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_bulk_action(
+    app: AppHandle,
+    state: State<'_, DockerManagerState>,
+    request: BulkActionRequest,
+) -> Result<Vec<BulkActionResult>, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager
+        .bulk_action(request, |progress| {
+            let _ = app.emit("bulk-action-progress", progress);
+        })
+        .await
+    {
+        Ok(results) => {
+            set_docker_manager(&state, manager).await;
+            Ok(results)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_recreate_container(
+    state: State<'_, DockerManagerState>,
+    request: RecreateContainerRequest,
+) -> Result<RecreateContainerResult, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.recreate_container(request).await {
+        Ok(result) => {
+            set_docker_manager(&state, manager).await;
+            Ok(result)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_container_run_snippets(
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    reveal_secrets: Option<bool>,
+) -> Result<ContainerRunSnippets, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager
+        .container_run_snippets(&container_id, reveal_secrets.unwrap_or(false))
+        .await
+    {
+        Ok(snippets) => {
+            set_docker_manager(&state, manager).await;
+            Ok(snippets)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_container_environment(
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    reveal_secrets: Option<bool>,
+) -> Result<Vec<EnvVar>, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager
+        .container_environment(&container_id, reveal_secrets.unwrap_or(false))
+        .await
+    {
+        Ok(vars) => {
+            set_docker_manager(&state, manager).await;
+            Ok(vars)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+fn docker_parse_env_file(path: String) -> Result<Vec<EnvVar>, String> {
+    let content = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+    parse_env_file(&content).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_run_task(
+    app: AppHandle,
+    state: State<'_, DockerManagerState>,
+    request: RunTaskRequest,
+) -> Result<RunTaskResult, String> {
+    let manager = get_docker_manager(&state).await?;
+    let result = manager
+        .run_task(request, |line| {
+            let _ = app.emit("task-log", line);
+        })
+        .await;
+    set_docker_manager(&state, manager).await;
+
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_inspect_image(
+    state: State<'_, DockerManagerState>,
+    image: String,
+    reveal_secrets: Option<bool>,
+) -> Result<ImageDetails, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager
+        .inspect_image(&image, reveal_secrets.unwrap_or(false))
+        .await
+    {
+        Ok(details) => {
+            set_docker_manager(&state, manager).await;
+            Ok(details)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_tag_image(
+    state: State<'_, DockerManagerState>,
+    source: String,
+    target: String,
+) -> Result<String, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.tag_image(&source, &target).await {
+        Ok(_) => {
+            set_docker_manager(&state, manager).await;
+            Ok("Image tagged successfully".to_string())
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_untag_image(
+    state: State<'_, DockerManagerState>,
+    reference: String,
+) -> Result<String, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.untag_image(&reference).await {
+        Ok(_) => {
+            set_docker_manager(&state, manager).await;
+            Ok("Tag removed successfully".to_string())
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_push_image(
+    app: AppHandle,
+    state: State<'_, DockerManagerState>,
+    reference: String,
+    credentials: Option<RegistryCredentials>,
+) -> Result<String, String> {
+    let manager = get_docker_manager(&state).await?;
+    let result = manager
+        .push_image(&reference, credentials, |progress| {
+            let _ = app.emit("image-push-progress", progress);
+        })
+        .await;
+    set_docker_manager(&state, manager).await;
+
+    result
+        .map(|_| "Image pushed successfully".to_string())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_list_registry_logins() -> Result<Vec<RegistryLogin>, String> {
+    with_credential_store(|store| store.list_logins())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_registry_login(
+    registry: String,
+    username: String,
+    password: String,
+) -> Result<String, String> {
+    with_credential_store(move |store| store.login(&registry, &username, &password))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok("Login saved successfully".to_string())
+}
+
+#[tauri::command]
+async fn docker_registry_logout(registry: String) -> Result<String, String> {
+    with_credential_store(move |store| store.logout(&registry))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok("Logout successful".to_string())
+}
+
+#[tauri::command]
+async fn docker_registry_repositories(
+    connection: RegistryConnection,
+) -> Result<Vec<String>, String> {
+    let client = RegistryClient::new(&connection)
+        .await
+        .map_err(|e| e.to_string())?;
+    client.list_repositories().await.map_err(|e| e.to_string())
+}
+
+// Plataforma do daemon, para escolher o manifesto certo em índices multi-arquitetura
+async fn daemon_platform(state: &State<'_, DockerManagerState>) -> Result<String, String> {
+    let manager = get_docker_manager(state).await?;
+    let platform = manager.daemon_platform().await;
+    set_docker_manager(state, manager).await;
+    platform.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_registry_tags(
+    state: State<'_, DockerManagerState>,
+    connection: RegistryConnection,
+    repository: String,
+) -> Result<Vec<RegistryTag>, String> {
+    let client = RegistryClient::new(&connection)
+        .await
+        .map_err(|e| e.to_string())?;
+    let platform = daemon_platform(&state).await?;
+    client
+        .list_tag_details(&repository, &platform)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_registry_manifest(
+    state: State<'_, DockerManagerState>,
+    connection: RegistryConnection,
+    repository: String,
+    reference: String,
+) -> Result<RegistryManifest, String> {
+    let client = RegistryClient::new(&connection)
+        .await
+        .map_err(|e| e.to_string())?;
+    let platform = daemon_platform(&state).await?;
+    client
+        .manifest(&repository, &reference, &platform)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_registry_delete_manifest(
+    connection: RegistryConnection,
+    repository: String,
+    reference: String,
+) -> Result<String, String> {
+    let client = RegistryClient::new(&connection)
+        .await
+        .map_err(|e| e.to_string())?;
+    client
+        .delete_manifest(&repository, &reference)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_registry_pull(
+    state: State<'_, DockerManagerState>,
+    connection: RegistryConnection,
+    repository: String,
+    tag: String,
+) -> Result<String, String> {
+    let client = RegistryClient::new(&connection)
+        .await
+        .map_err(|e| e.to_string())?;
+    let image = client.image_reference(&repository, &tag);
+
+    let manager = get_docker_manager(&state).await?;
+    match manager.pull_image(&image, client.pull_credentials()).await {
+        Ok(_) => {
+            set_docker_manager(&state, manager).await;
+            Ok(image)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_preview_prune_images(
+    state: State<'_, DockerManagerState>,
+    request: PruneImagesRequest,
+) -> Result<PruneImagesPreview, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.preview_prune_images(&request).await {
+        Ok(preview) => {
+            set_docker_manager(&state, manager).await;
+            Ok(preview)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_prune_images(
+    state: State<'_, DockerManagerState>,
+    request: PruneImagesRequest,
+) -> Result<PruneImagesResult, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.prune_images(&request).await {
+        Ok(result) => {
+            set_docker_manager(&state, manager).await;
+            Ok(result)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_check_image_updates(
+    state: State<'_, DockerManagerState>,
+) -> Result<ImageUpdateReport, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.check_image_updates().await {
+        Ok(report) => {
+            set_docker_manager(&state, manager).await;
+            Ok(report)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+// Registra o token da operação; um id repetido substituiria o token de outra em andamento
+async fn register_cancellation(
+    cancellations: &CancellationState,
+    operation_id: &str,
+) -> Result<CancellationToken, String> {
+    let mut cancellations = cancellations.lock().await;
+    if cancellations.contains_key(operation_id) {
+        return Err(format!("Operação já em andamento: {}", operation_id));
+    }
+
+    let cancel = CancellationToken::new();
+    cancellations.insert(operation_id.to_string(), cancel.clone());
+    Ok(cancel)
+}
+
+#[tauri::command]
+async fn docker_save_images(
+    app: AppHandle,
+    state: State<'_, DockerManagerState>,
+    cancellations: State<'_, CancellationState>,
+    operation_id: String,
+    images: Vec<String>,
+    destination: String,
+) -> Result<u64, String> {
+    let cancel = register_cancellation(&cancellations, &operation_id).await?;
+    let manager = match get_docker_manager(&state).await {
+        Ok(manager) => manager,
+        Err(e) => {
+            cancellations.lock().await.remove(&operation_id);
+            return Err(e);
+        }
+    };
+    let result = manager
+        .save_images(&operation_id, &images, &destination, cancel, |progress| {
+            let _ = app.emit("image-archive-progress", progress);
+        })
+        .await;
+    set_docker_manager(&state, manager).await;
+    cancellations.lock().await.remove(&operation_id);
+
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_load_images(
+    app: AppHandle,
+    state: State<'_, DockerManagerState>,
+    cancellations: State<'_, CancellationState>,
+    operation_id: String,
+    source: String,
+) -> Result<LoadImagesResult, String> {
+    let cancel = register_cancellation(&cancellations, &operation_id).await?;
+    let manager = match get_docker_manager(&state).await {
+        Ok(manager) => manager,
+        Err(e) => {
+            cancellations.lock().await.remove(&operation_id);
+            return Err(e);
+        }
+    };
+    let progress_app = app.clone();
+    let result = manager
+        .load_images(&operation_id, &source, cancel, move |progress| {
+            let _ = progress_app.emit("image-archive-progress", progress);
+        })
+        .await;
+    set_docker_manager(&state, manager).await;
+    cancellations.lock().await.remove(&operation_id);
+
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_cancel_operation(
+    cancellations: State<'_, CancellationState>,
+    operation_id: String,
+) -> Result<String, String> {
+    match cancellations.lock().await.remove(&operation_id) {
+        Some(cancel) => {
+            cancel.cancel();
+            Ok("Operation cancelled".to_string())
+        }
+        None => Err(format!("Operação não encontrada: {}", operation_id)),
+    }
+}
+
+#[tauri::command]
+async fn docker_build_image(
+    app: AppHandle,
+    state: State<'_, DockerManagerState>,
+    request: BuildImageRequest,
+) -> Result<BuildImageResult, String> {
+    let manager = get_docker_manager(&state).await?;
+    let result = manager
+        .build_image(request, |line| {
+            let _ = app.emit("image-build-log", line);
+        })
+        .await;
+    set_docker_manager(&state, manager).await;
+
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_container_health(
+    state: State<'_, DockerManagerState>,
+    container_id: String,
+    last: Option<usize>,
+) -> Result<ContainerHealth, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.container_health(&container_id, last).await {
+        Ok(health) => {
+            set_docker_manager(&state, manager).await;
+            Ok(health)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+// Inicia (uma única vez) o envio do evento "container-health-changed"
+#[tauri::command]
+async fn docker_subscribe_health_events(
+    app: AppHandle,
+    state: State<'_, DockerManagerState>,
+    watcher: State<'_, HealthWatcherState>,
+) -> Result<String, String> {
+    let mut watcher_guard = watcher.lock().await;
+    if watcher_guard
+        .as_ref()
+        .is_some_and(|handle| !handle.inner().is_finished())
+    {
+        return Ok("Already subscribed to health events".to_string());
+    }
+
+    let manager = get_docker_manager(&state).await?;
+    let events = manager.health_events();
+    set_docker_manager(&state, manager).await;
+
+    let handle = tauri::async_runtime::spawn(async move {
+        use futures_util::StreamExt;
+
+        futures_util::pin_mut!(events);
+        while let Some(event) = events.next().await {
+            match event {
+                Ok(event) => {
+                    let _ = app.emit("container-health-changed", event);
+                }
+                Err(e) => {
+                    let _ = app.emit("container-health-error", e.to_string());
+                    break;
+                }
+            }
+        }
+    });
+    *watcher_guard = Some(handle);
+
+    Ok("Subscribed to health events".to_string())
+}
+
+#[tauri::command]
+async fn docker_unsubscribe_health_events(
+    watcher: State<'_, HealthWatcherState>,
+) -> Result<String, String> {
+    if let Some(handle) = watcher.lock().await.take() {
+        handle.abort();
+    }
+    Ok("Unsubscribed from health events".to_string())
+}
+
+#[tauri::command]
+fn docker_parse_run_command(command: String) -> Result<DockerRunImport, String> {
+    parse_docker_run(&command).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_check_ports(
+    state: State<'_, DockerManagerState>,
+    ports: Vec<PortMapping>,
+) -> Result<PortCheckResult, String> {
+    let manager = get_docker_manager(&state).await?;
+    match manager.check_ports(&ports).await {
+        Ok(result) => {
+            set_docker_manager(&state, manager).await;
+            Ok(result)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+#[tauri::command]
+async fn docker_find_free_port(
+    state: State<'_, DockerManagerState>,
+    protocol: Option<String>,
+    preferred: Option<u16>,
+) -> Result<u16, String> {
+    let manager = get_docker_manager(&state).await?;
+    let protocol = protocol.unwrap_or_else(|| "tcp".to_string());
+    match manager.find_free_port(&protocol, preferred).await {
+        Ok(port) => {
+            set_docker_manager(&state, manager).await;
+            Ok(port)
+        }
+        Err(e) => {
+            set_docker_manager(&state, manager).await;
+            Err(e.to_string())
+        }
+    }
+}
+
+// Templates ficam no diretório de configuração do app
+fn template_store(app: &AppHandle) -> Result<TemplateStore, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(TemplateStore::new(&config_dir))
+}
+
+#[tauri::command]
+fn docker_list_templates(app: AppHandle) -> Result<Vec<ContainerTemplate>, String> {
+    let mut templates = template_store(&app)?.list().map_err(|e| e.to_string())?;
+    templates.iter_mut().for_each(mask_template_secrets);
+    Ok(templates)
+}
+
+#[tauri::command]
+fn docker_get_template(
+    app: AppHandle,
+    name: String,
+    reveal_secrets: Option<bool>,
+) -> Result<ContainerTemplate, String> {
+    let mut template = template_store(&app)?
+        .get(&name)
+        .map_err(|e| e.to_string())?;
+    if !reveal_secrets.unwrap_or(false) {
+        mask_template_secrets(&mut template);
+    }
+    Ok(template)
+}
+
+#[tauri::command]
+fn docker_save_template(
+    app: AppHandle,
+    template: ContainerTemplate,
+) -> Result<ContainerTemplate, String> {
+    let mut saved = template_store(&app)?
+        .save(template)
+        .map_err(|e| e.to_string())?;
+    mask_template_secrets(&mut saved);
+    Ok(saved)
+}
+
+#[tauri::command]
+fn docker_delete_template(app: AppHandle, name: String) -> Result<String, String> {
+    template_store(&app)?
+        .delete(&name)
+        .map_err(|e| e.to_string())?;
+    Ok("Template removed successfully".to_string())
+}
+
+#[tauri::command]
+fn docker_export_templates(
+    app: AppHandle,
+    names: Option<Vec<String>>,
+    destination: String,
+) -> Result<usize, String> {
+    template_store(&app)?
+        .export(&names.unwrap_or_default(), Path::new(&destination))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn docker_import_templates(
+    app: AppHandle,
+    source: String,
+    overwrite: Option<bool>,
+) -> Result<TemplateImportResult, String> {
+    template_store(&app)?
+        .import(Path::new(&source), overwrite.unwrap_or(false))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn docker_create_from_template(
+    state: State<'_, DockerManagerState>,
+    app: AppHandle,
+    name: String,
+    variables: HashMap<String, String>,
+) -> Result<CreateContainerResult, String> {
+    let template = template_store(&app)?
+        .get(&name)
+        .map_err(|e| e.to_string())?;
+    let request = render_template(&template, &variables).map_err(|e| e.to_string())?;
+
+    let manager = get_docker_manager(&state).await?;
+    let result = manager.create_container(request).await;
+    set_docker_manager(&state, manager).await;
+
+    result.map_err(|e| e.to_string())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .manage(DockerManagerState::default())
+        .manage(HealthWatcherState::default())
+        .manage(CancellationState::default())
         .invoke_handler(tauri::generate_handler![
             docker_status,
             docker_infos,
@@ -450,6 +1374,8 @@ pub fn run() {
             docker_unpause_container,
             docker_restart_container,
             docker_remove_container,
+            docker_rename_container,
+            docker_kill_container,
             docker_create_container,
             docker_list_images,
             docker_remove_image,
@@ -461,6 +1387,52 @@ pub fn run() {
             docker_remove_network,
             docker_create_network,
             docker_get_container_stats_for_graph,
+            docker_list_container_files,
+            docker_download_from_container,
+            docker_upload_to_container,
+            docker_container_top,
+            docker_signal_container_process,
+            docker_container_changes,
+            docker_commit_container,
+            docker_update_container,
+            docker_bulk_action,
+            docker_recreate_container,
+            docker_container_run_snippets,
+            docker_parse_run_command,
+            docker_list_templates,
+            docker_get_template,
+            docker_save_template,
+            docker_delete_template,
+            docker_export_templates,
+            docker_import_templates,
+            docker_create_from_template,
+            docker_check_ports,
+            docker_find_free_port,
+            docker_container_environment,
+            docker_parse_env_file,
+            docker_container_health,
+            docker_subscribe_health_events,
+            docker_unsubscribe_health_events,
+            docker_run_task,
+            docker_build_image,
+            docker_inspect_image,
+            docker_tag_image,
+            docker_untag_image,
+            docker_push_image,
+            docker_list_registry_logins,
+            docker_registry_login,
+            docker_registry_logout,
+            docker_save_images,
+            docker_load_images,
+            docker_cancel_operation,
+            docker_check_image_updates,
+            docker_registry_repositories,
+            docker_registry_tags,
+            docker_registry_manifest,
+            docker_registry_delete_manifest,
+            docker_registry_pull,
+            docker_preview_prune_images,
+            docker_prune_images,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
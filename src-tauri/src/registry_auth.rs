@@ -0,0 +1,419 @@
+// Credenciais de registries a partir do ~/.docker/config.json e dos credential helpers
+use anyhow::{Context, Result};
+use base64::Engine;
+use bollard::auth::DockerCredentials;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+// Chave usada pelo Docker Hub no config.json
+pub const DOCKER_HUB_REGISTRY: &str = "https://index.docker.io/v1/";
+
+// Usuário especial que indica identity token nos credential helpers
+const IDENTITY_TOKEN_USERNAME: &str = "<token>";
+
+// Mensagem dos credential helpers quando não há login salvo para o registry
+const HELPER_NOT_FOUND: &str = "credentials not found";
+
+// Login conhecido (sem o segredo)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegistryLogin {
+    pub registry: String,
+    pub username: String,
+    pub source: String, // config, credsStore:<helper> ou credHelpers:<helper>
+}
+
+// Resposta do `docker-credential-<helper> get`
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct HelperCredentials {
+    username: String,
+    secret: String,
+}
+
+pub struct CredentialStore {
+    config_path: PathBuf,
+}
+
+impl CredentialStore {
+    // Usa $DOCKER_CONFIG, como a CLI do Docker, ou ~/.docker
+    pub fn from_env() -> Self {
+        let config_dir = std::env::var_os("DOCKER_CONFIG")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".docker")))
+            .unwrap_or_else(|| PathBuf::from(".docker"));
+
+        CredentialStore {
+            config_path: config_dir.join("config.json"),
+        }
+    }
+
+    // Lista os registries com login salvo, em qualquer uma das fontes
+    pub fn list_logins(&self) -> Result<Vec<RegistryLogin>> {
+        let config = self.read_config()?;
+        let mut logins: Vec<RegistryLogin> = Vec::new();
+
+        // Logins do credsStore (o helper informa registry -> usuário)
+        if let Some(helper) = config.get("credsStore").and_then(Value::as_str) {
+            let output = run_helper(helper, "list", "")?;
+            let listed: HashMap<String, String> = serde_json::from_str(&output)
+                .context(format!("Resposta inválida de docker-credential-{}", helper))?;
+            for (registry, username) in listed {
+                logins.push(RegistryLogin {
+                    registry,
+                    username,
+                    source: format!("credsStore:{}", helper),
+                });
+            }
+        }
+
+        // Registries com helper próprio
+        for (registry, helper) in object_entries(&config, "credHelpers") {
+            let Some(helper) = helper.as_str() else {
+                continue;
+            };
+            let username = run_helper(helper, "get", &registry)
+                .ok()
+                .and_then(|output| serde_json::from_str::<HelperCredentials>(&output).ok())
+                .map(|credentials| credentials.username)
+                .unwrap_or_default();
+            logins.retain(|login| {
+                normalize_registry(&login.registry) != normalize_registry(&registry)
+            });
+            logins.push(RegistryLogin {
+                registry,
+                username,
+                source: format!("credHelpers:{}", helper),
+            });
+        }
+
+        // Credenciais gravadas diretamente no config.json
+        for (registry, entry) in object_entries(&config, "auths") {
+            let username = entry
+                .get("auth")
+                .and_then(Value::as_str)
+                .and_then(decode_auth)
+                .map(|(username, _)| username)
+                .or_else(|| {
+                    entry
+                        .get("username")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                });
+            let Some(username) = username else {
+                continue;
+            };
+            if logins
+                .iter()
+                .any(|login| normalize_registry(&login.registry) == normalize_registry(&registry))
+            {
+                continue;
+            }
+            logins.push(RegistryLogin {
+                registry,
+                username,
+                source: "config".to_string(),
+            });
+        }
+
+        logins.sort_by(|a, b| a.registry.cmp(&b.registry));
+        Ok(logins)
+    }
+
+    // Credenciais do registry de uma imagem (ex.: "ghcr.io/org/app:1.0")
+    pub fn credentials_for_image(&self, image: &str) -> Result<Option<DockerCredentials>> {
+        self.credentials_for(&registry_from_image(image))
+    }
+
+    // Credenciais de um registry, seguindo a ordem da CLI: credHelpers, credsStore e auths
+    pub fn credentials_for(&self, registry: &str) -> Result<Option<DockerCredentials>> {
+        let config = self.read_config()?;
+        let key = normalize_registry(registry);
+
+        if let Some(helper) = self.helper_for(&config, registry) {
+            let server = registry_server_address(registry);
+            // Helper sem login para o registry não é erro; falhas reais (keychain
+            // bloqueado, helper ausente) são repassadas em vez de virar pull anônimo
+            match run_helper(&helper, "get", &server) {
+                Ok(output) => {
+                    let credentials: HelperCredentials = serde_json::from_str(&output)
+                        .context(format!("Resposta inválida de docker-credential-{}", helper))?;
+                    return Ok(Some(helper_to_docker_credentials(credentials, &server)));
+                }
+                Err(e) if e.to_string().contains(HELPER_NOT_FOUND) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let entry = object_entries(&config, "auths")
+            .into_iter()
+            .find(|(name, _)| normalize_registry(name) == key);
+        let Some((server, entry)) = entry else {
+            return Ok(None);
+        };
+
+        let (username, password) = entry
+            .get("auth")
+            .and_then(Value::as_str)
+            .and_then(decode_auth)
+            .map(|(username, password)| (Some(username), Some(password)))
+            .unwrap_or((None, None));
+        let identity_token = entry
+            .get("identitytoken")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        if username.is_none() && identity_token.is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(DockerCredentials {
+            username,
+            password,
+            identitytoken: identity_token,
+            serveraddress: Some(server),
+            ..Default::default()
+        }))
+    }
+
+    // Todas as credenciais conhecidas, por registry (usado no build, que pode puxar várias imagens)
+    pub fn all_credentials(&self) -> Result<HashMap<String, DockerCredentials>> {
+        let mut credentials = HashMap::new();
+        for login in self.list_logins()? {
+            if let Some(found) = self.credentials_for(&login.registry)? {
+                credentials.insert(login.registry, found);
+            }
+        }
+        Ok(credentials)
+    }
+
+    // Salva um login no helper configurado ou, sem helper, no próprio config.json
+    pub fn login(&self, registry: &str, username: &str, password: &str) -> Result<()> {
+        if username.trim().is_empty() || password.is_empty() {
+            return Err(anyhow::anyhow!("Usuário e senha são obrigatórios"));
+        }
+
+        let mut config = self.read_config()?;
+        let server = registry_server_address(registry);
+
+        if let Some(helper) = self.helper_for(&config, registry) {
+            let payload = serde_json::json!({
+                "ServerURL": server,
+                "Username": username,
+                "Secret": password,
+            });
+            run_helper(&helper, "store", &payload.to_string())?;
+
+            // Garante que a entrada exista em "auths", como a CLI faz
+            auths_mut(&mut config)
+                .entry(server)
+                .or_insert_with(|| Value::Object(Map::new()));
+        } else {
+            let auth = base64::engine::general_purpose::STANDARD
+                .encode(format!("{}:{}", username, password));
+            auths_mut(&mut config).insert(server, serde_json::json!({ "auth": auth }));
+        }
+
+        self.write_config(&config)
+    }
+
+    // Remove o login do helper e do config.json
+    pub fn logout(&self, registry: &str) -> Result<()> {
+        let mut config = self.read_config()?;
+        let key = normalize_registry(registry);
+
+        if let Some(helper) = self.helper_for(&config, registry) {
+            run_helper(&helper, "erase", &registry_server_address(registry))?;
+        }
+
+        let auths = auths_mut(&mut config);
+        let before = auths.len();
+        auths.retain(|name, _| normalize_registry(name) != key);
+        let removed = auths.len() != before;
+
+        if !removed && self.helper_for(&config, registry).is_none() {
+            return Err(anyhow::anyhow!("Nenhum login encontrado para {}", registry));
+        }
+
+        self.write_config(&config)
+    }
+
+    // Helper responsável pelo registry: credHelpers tem prioridade sobre credsStore
+    fn helper_for(&self, config: &Value, registry: &str) -> Option<String> {
+        let key = normalize_registry(registry);
+        object_entries(config, "credHelpers")
+            .into_iter()
+            .find(|(name, _)| normalize_registry(name) == key)
+            .and_then(|(_, helper)| helper.as_str().map(str::to_string))
+            .or_else(|| {
+                config
+                    .get("credsStore")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            })
+    }
+
+    fn read_config(&self) -> Result<Value> {
+        if !self.config_path.exists() {
+            return Ok(Value::Object(Map::new()));
+        }
+
+        let content = std::fs::read_to_string(&self.config_path)
+            .context(format!("Falha ao ler {}", self.config_path.display()))?;
+        if content.trim().is_empty() {
+            return Ok(Value::Object(Map::new()));
+        }
+
+        serde_json::from_str(&content).context(format!(
+            "Arquivo de configuração do Docker inválido: {}",
+            self.config_path.display()
+        ))
+    }
+
+    // Preserva as demais chaves do config.json e grava de forma atômica
+    fn write_config(&self, config: &Value) -> Result<()> {
+        if let Some(parent) = self.config_path.parent() {
+            std::fs::create_dir_all(parent)
+                .context(format!("Falha ao criar {}", parent.display()))?;
+        }
+
+        let temp_path = self.config_path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_string_pretty(config)?)
+            .context(format!("Falha ao salvar {}", temp_path.display()))?;
+        std::fs::rename(&temp_path, &self.config_path)
+            .context(format!("Falha ao salvar {}", self.config_path.display()))?;
+
+        Ok(())
+    }
+}
+
+// Executa uma operação do CredentialStore fora do runtime assíncrono: a leitura do
+// config.json e os credential helpers (processos externos) são bloqueantes
+pub async fn with_credential_store<T, F>(operation: F) -> Result<T>
+where
+    F: FnOnce(&CredentialStore) -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(move || operation(&CredentialStore::from_env()))
+        .await
+        .context("Falha ao consultar as credenciais do Docker")?
+}
+
+// Registry de uma referência de imagem; sem host explícito é o Docker Hub
+pub fn registry_from_image(image: &str) -> String {
+    match image.split_once('/') {
+        Some((first, _)) if first.contains('.') || first.contains(':') || first == "localhost" => {
+            first.to_string()
+        }
+        _ => DOCKER_HUB_REGISTRY.to_string(),
+    }
+}
+
+// Compara registries ignorando esquema, "/v1/" e os aliases do Docker Hub
+fn normalize_registry(registry: &str) -> String {
+    let host = registry
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let host = host.split('/').next().unwrap_or(host).to_lowercase();
+
+    match host.as_str() {
+        "docker.io" | "index.docker.io" | "registry-1.docker.io" => "docker.io".to_string(),
+        _ => host,
+    }
+}
+
+// Endereço gravado no config.json (o Docker Hub usa a URL completa do índice)
+fn registry_server_address(registry: &str) -> String {
+    if normalize_registry(registry) == "docker.io" {
+        DOCKER_HUB_REGISTRY.to_string()
+    } else {
+        normalize_registry(registry)
+    }
+}
+
+fn object_entries(config: &Value, key: &str) -> Vec<(String, Value)> {
+    config
+        .get(key)
+        .and_then(Value::as_object)
+        .map(|map| map.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+        .unwrap_or_default()
+}
+
+fn auths_mut(config: &mut Value) -> &mut Map<String, Value> {
+    if !config.is_object() {
+        *config = Value::Object(Map::new());
+    }
+    let root = config.as_object_mut().expect("config é um objeto");
+    let auths = root
+        .entry("auths")
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !auths.is_object() {
+        *auths = Value::Object(Map::new());
+    }
+    auths.as_object_mut().expect("auths é um objeto")
+}
+
+// "auth" do config.json é base64("usuário:senha")
+fn decode_auth(auth: &str) -> Option<(String, String)> {
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(auth.trim())
+        .ok()?;
+    let decoded = String::from_utf8(decoded).ok()?;
+    let (username, password) = decoded.split_once(':')?;
+    Some((username.to_string(), password.to_string()))
+}
+
+fn helper_to_docker_credentials(credentials: HelperCredentials, server: &str) -> DockerCredentials {
+    if credentials.username == IDENTITY_TOKEN_USERNAME {
+        return DockerCredentials {
+            identitytoken: Some(credentials.secret),
+            serveraddress: Some(server.to_string()),
+            ..Default::default()
+        };
+    }
+
+    DockerCredentials {
+        username: Some(credentials.username),
+        password: Some(credentials.secret),
+        serveraddress: Some(server.to_string()),
+        ..Default::default()
+    }
+}
+
+// Executa `docker-credential-<helper> <ação>` enviando `input` pelo stdin
+fn run_helper(helper: &str, action: &str, input: &str) -> Result<String> {
+    let program = format!("docker-credential-{}", helper);
+    let mut child = Command::new(&program)
+        .arg(action)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context(format!("Falha ao executar {}", program))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .context(format!("Falha ao enviar dados para {}", program))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .context(format!("Falha ao executar {}", program))?;
+    if !output.status.success() {
+        let message = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(anyhow::anyhow!(
+            "{} {} falhou: {}",
+            program,
+            action,
+            if message.is_empty() { stderr } else { message }
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
@@ -0,0 +1,602 @@
+// Cliente da API HTTP v2 de registries (catálogo, tags e manifestos)
+use crate::docker::RegistryCredentials;
+use crate::registry_auth::with_credential_store;
+use anyhow::{Context, Result};
+use reqwest::{header, Method, RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// Tipos de manifesto aceitos, do mais específico para o índice multi-arquitetura
+const MANIFEST_MEDIA_TYPES: &[&str] = &[
+    "application/vnd.docker.distribution.manifest.v2+json",
+    "application/vnd.docker.distribution.manifest.list.v2+json",
+    "application/vnd.oci.image.manifest.v1+json",
+    "application/vnd.oci.image.index.v1+json",
+];
+
+// Itens por página no catálogo e na lista de tags
+const PAGE_SIZE: usize = 100;
+
+// Endereço e login informados pelo usuário; sem login, usa o salvo no config.json
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RegistryConnection {
+    pub url: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    // Registry sem TLS (ex.: registry:2 na rede local), acessado por HTTP
+    #[serde(default)]
+    pub insecure: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegistryLayer {
+    pub digest: String,
+    pub media_type: Option<String>,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegistryManifest {
+    pub repository: String,
+    pub reference: String,
+    pub digest: Option<String>,
+    pub media_type: Option<String>,
+    // Config + camadas; em índices multi-arquitetura, da plataforma do daemon
+    pub size: u64,
+    pub platforms: Vec<String>,
+    pub layers: Vec<RegistryLayer>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegistryTag {
+    pub tag: String,
+    pub digest: Option<String>,
+    pub media_type: Option<String>,
+    pub size: u64,
+    pub platforms: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ManifestDocument {
+    media_type: Option<String>,
+    config: Option<Descriptor>,
+    #[serde(default)]
+    layers: Vec<Descriptor>,
+    #[serde(default)]
+    manifests: Vec<Descriptor>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    media_type: Option<String>,
+    digest: String,
+    #[serde(default)]
+    size: u64,
+    platform: Option<Platform>,
+}
+
+#[derive(Deserialize)]
+struct Platform {
+    architecture: String,
+    os: String,
+    variant: Option<String>,
+}
+
+impl Platform {
+    fn name(&self) -> String {
+        match &self.variant {
+            Some(variant) => format!("{}/{}/{}", self.os, self.architecture, variant),
+            None => format!("{}/{}", self.os, self.architecture),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CatalogPage {
+    #[serde(default)]
+    repositories: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct TagsPage {
+    // O registry devolve null quando o repositório não tem tags
+    tags: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    token: Option<String>,
+    access_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RegistryErrors {
+    #[serde(default)]
+    errors: Vec<RegistryError>,
+}
+
+#[derive(Deserialize)]
+struct RegistryError {
+    code: String,
+    message: String,
+}
+
+enum Authorization {
+    Anonymous,
+    Basic,
+    Bearer(String),
+}
+
+pub struct RegistryClient {
+    http: reqwest::Client,
+    base_url: String,
+    host: String,
+    credentials: Option<(String, String)>,
+    // Último token obtido pelo desafio Bearer; renovado a cada 401
+    token: Mutex<Option<String>>,
+}
+
+impl RegistryClient {
+    pub async fn new(connection: &RegistryConnection) -> Result<Self> {
+        let url = connection.url.trim().trim_end_matches('/');
+        if url.is_empty() {
+            return Err(anyhow::anyhow!("Endereço do registry não informado"));
+        }
+
+        // Sem esquema, localhost e registries marcados como inseguros usam HTTP (como o
+        // Docker trata registries locais e os listados em insecure-registries)
+        let base_url = if url.starts_with("http://") || url.starts_with("https://") {
+            url.to_string()
+        } else if connection.insecure || url.starts_with("localhost") || url.starts_with("127.") {
+            format!("http://{}", url)
+        } else {
+            format!("https://{}", url)
+        };
+        let host = base_url
+            .split_once("://")
+            .map(|(_, host)| host.to_string())
+            .unwrap_or_default();
+
+        let credentials = match (&connection.username, &connection.password) {
+            (Some(username), Some(password)) if !username.is_empty() => {
+                Some((username.clone(), password.clone()))
+            }
+            _ => {
+                let registry = host.clone();
+                with_credential_store(move |store| store.credentials_for(&registry))
+                    .await
+                    .context("Falha ao obter as credenciais do registry")?
+                    .and_then(|credentials| credentials.username.zip(credentials.password))
+            }
+        };
+
+        let http = reqwest::Client::builder()
+            .build()
+            .context("Falha ao criar cliente HTTP")?;
+
+        Ok(RegistryClient {
+            http,
+            base_url,
+            host,
+            credentials,
+            token: Mutex::new(None),
+        })
+    }
+
+    // Referência usada para o pull no daemon: host/repositório:tag
+    pub fn image_reference(&self, repository: &str, tag: &str) -> String {
+        format!("{}/{}:{}", self.host, repository, tag)
+    }
+
+    // Login repassado ao daemon no pull
+    pub fn pull_credentials(&self) -> Option<RegistryCredentials> {
+        self.credentials
+            .clone()
+            .map(|(username, password)| RegistryCredentials {
+                username: Some(username),
+                password: Some(password),
+                server_address: Some(self.host.clone()),
+                identity_token: None,
+            })
+    }
+
+    // Lista os repositórios do catálogo, seguindo a paginação pelo header Link
+    pub async fn list_repositories(&self) -> Result<Vec<String>> {
+        let mut repositories = Vec::new();
+        let mut next = Some(format!("/v2/_catalog?n={}", PAGE_SIZE));
+
+        while let Some(path) = next {
+            let response = self.send(Method::GET, &path, &[]).await?;
+            let response = ensure_success(response, "Falha ao listar repositórios").await?;
+            next = next_page(&response);
+            let page: CatalogPage = response
+                .json()
+                .await
+                .context("Resposta inválida do catálogo")?;
+            repositories.extend(page.repositories);
+        }
+
+        Ok(repositories)
+    }
+
+    pub async fn list_tags(&self, repository: &str) -> Result<Vec<String>> {
+        let mut tags = Vec::new();
+        let mut next = Some(format!("/v2/{}/tags/list?n={}", repository, PAGE_SIZE));
+
+        while let Some(path) = next {
+            let response = self.send(Method::GET, &path, &[]).await?;
+            let response =
+                ensure_success(response, &format!("Falha ao listar tags de {}", repository))
+                    .await?;
+            next = next_page(&response);
+            let page: TagsPage = response
+                .json()
+                .await
+                .context("Resposta inválida da lista de tags")?;
+            tags.extend(page.tags.unwrap_or_default());
+        }
+
+        tags.sort();
+        Ok(tags)
+    }
+
+    // Tags com digest e tamanho de cada uma (na plataforma do daemon, ex.: "linux/arm64")
+    pub async fn list_tag_details(
+        &self,
+        repository: &str,
+        platform: &str,
+    ) -> Result<Vec<RegistryTag>> {
+        let mut details = Vec::new();
+        for tag in self.list_tags(repository).await? {
+            let manifest = self.manifest(repository, &tag, platform).await?;
+            details.push(RegistryTag {
+                tag,
+                digest: manifest.digest,
+                media_type: manifest.media_type,
+                size: manifest.size,
+                platforms: manifest.platforms,
+            });
+        }
+        Ok(details)
+    }
+
+    // Manifesto de uma tag ou digest; índices são resolvidos para a plataforma informada
+    // (a do daemon, "os/arquitetura") para calcular o tamanho
+    pub async fn manifest(
+        &self,
+        repository: &str,
+        reference: &str,
+        platform: &str,
+    ) -> Result<RegistryManifest> {
+        let (digest, document) = self.fetch_manifest(repository, reference).await?;
+
+        let platforms: Vec<String> = document
+            .manifests
+            .iter()
+            .filter_map(|manifest| manifest.platform.as_ref())
+            .map(Platform::name)
+            .filter(|platform| platform != "unknown/unknown")
+            .collect();
+
+        // Índice: usa o manifesto da plataforma pedida (ou o primeiro) para o tamanho e as
+        // camadas; a variante (ex.: arm/v7) é opcional na comparação
+        let (os, architecture) = platform.split_once('/').unwrap_or((platform, ""));
+        let architecture = architecture.split('/').next().unwrap_or_default();
+        let image_document = if document.manifests.is_empty() {
+            document
+        } else {
+            let chosen = document
+                .manifests
+                .iter()
+                .find(|manifest| {
+                    manifest.platform.as_ref().is_some_and(|candidate| {
+                        candidate.os == os && candidate.architecture == architecture
+                    })
+                })
+                .or_else(|| document.manifests.first())
+                .map(|manifest| manifest.digest.clone())
+                .unwrap_or_default();
+            let (_, child) = self.fetch_manifest(repository, &chosen).await?;
+            ManifestDocument {
+                media_type: document.media_type,
+                ..child
+            }
+        };
+
+        let size = image_document
+            .config
+            .as_ref()
+            .map(|config| config.size)
+            .unwrap_or_default()
+            + image_document
+                .layers
+                .iter()
+                .map(|layer| layer.size)
+                .sum::<u64>();
+
+        Ok(RegistryManifest {
+            repository: repository.to_string(),
+            reference: reference.to_string(),
+            digest,
+            media_type: image_document.media_type,
+            size,
+            platforms,
+            layers: image_document
+                .layers
+                .into_iter()
+                .map(|layer| RegistryLayer {
+                    digest: layer.digest,
+                    media_type: layer.media_type,
+                    size: layer.size,
+                })
+                .collect(),
+        })
+    }
+
+    // Remove um manifesto (a tag é resolvida para o digest, exigido pela API)
+    pub async fn delete_manifest(&self, repository: &str, reference: &str) -> Result<String> {
+        let digest = if reference.contains(':') {
+            reference.to_string()
+        } else {
+            let path = format!("/v2/{}/manifests/{}", repository, reference);
+            let response = self.send(Method::HEAD, &path, MANIFEST_MEDIA_TYPES).await?;
+            let response = ensure_success(
+                response,
+                &format!("Manifesto não encontrado: {}:{}", repository, reference),
+            )
+            .await?;
+            content_digest(&response)
+                .ok_or_else(|| anyhow::anyhow!("Registry não informou o digest de {}", reference))?
+        };
+
+        let path = format!("/v2/{}/manifests/{}", repository, digest);
+        let response = self.send(Method::DELETE, &path, &[]).await?;
+        if response.status() == StatusCode::METHOD_NOT_ALLOWED {
+            return Err(anyhow::anyhow!(
+                "O registry não permite exclusão (habilite REGISTRY_STORAGE_DELETE_ENABLED=true)"
+            ));
+        }
+        ensure_success(response, &format!("Falha ao remover manifesto {}", digest)).await?;
+
+        Ok(digest)
+    }
+
+    async fn fetch_manifest(
+        &self,
+        repository: &str,
+        reference: &str,
+    ) -> Result<(Option<String>, ManifestDocument)> {
+        let path = format!("/v2/{}/manifests/{}", repository, reference);
+        let response = self.send(Method::GET, &path, MANIFEST_MEDIA_TYPES).await?;
+        let response = ensure_success(
+            response,
+            &format!("Manifesto não encontrado: {}:{}", repository, reference),
+        )
+        .await?;
+
+        let digest = content_digest(&response);
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let mut document: ManifestDocument = response.json().await.context("Manifesto inválido")?;
+        if document.media_type.is_none() {
+            document.media_type = content_type;
+        }
+
+        Ok((digest, document))
+    }
+
+    // Envia a requisição e, se o registry pedir autenticação, repete com Basic ou Bearer
+    async fn send(&self, method: Method, path: &str, accept: &[&str]) -> Result<Response> {
+        let cached_token = self.token.lock().unwrap().clone();
+        let first_authorization = match cached_token {
+            Some(token) => Authorization::Bearer(token),
+            None if self.credentials.is_some() => Authorization::Basic,
+            None => Authorization::Anonymous,
+        };
+
+        let response = self
+            .request(method.clone(), path, accept, &first_authorization)
+            .send()
+            .await
+            .context(format!("Falha ao acessar o registry {}", self.base_url))?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let challenge = response
+            .headers()
+            .get(header::WWW_AUTHENTICATE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        let (scheme, params) = parse_challenge(&challenge);
+
+        let authorization = match scheme.as_str() {
+            "bearer" => {
+                let token = self.fetch_token(&params).await?;
+                *self.token.lock().unwrap() = Some(token.clone());
+                Authorization::Bearer(token)
+            }
+            "basic" if self.credentials.is_some() => {
+                if matches!(first_authorization, Authorization::Basic) {
+                    return Err(anyhow::anyhow!(
+                        "Usuário ou senha inválidos para {}",
+                        self.host
+                    ));
+                }
+                Authorization::Basic
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "O registry {} exige autenticação; informe usuário e senha",
+                    self.host
+                ))
+            }
+        };
+
+        let response = self
+            .request(method, path, accept, &authorization)
+            .send()
+            .await
+            .context(format!("Falha ao acessar o registry {}", self.base_url))?;
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(anyhow::anyhow!("Acesso negado pelo registry {}", self.host));
+        }
+
+        Ok(response)
+    }
+
+    fn request(
+        &self,
+        method: Method,
+        path: &str,
+        accept: &[&str],
+        authorization: &Authorization,
+    ) -> RequestBuilder {
+        let url = if path.starts_with("http://") || path.starts_with("https://") {
+            path.to_string()
+        } else {
+            format!("{}{}", self.base_url, path)
+        };
+
+        let mut request = self.http.request(method, url);
+        if !accept.is_empty() {
+            request = request.header(header::ACCEPT, accept.join(", "));
+        }
+        match authorization {
+            Authorization::Anonymous => request,
+            Authorization::Basic => match &self.credentials {
+                Some((username, password)) => request.basic_auth(username, Some(password)),
+                None => request,
+            },
+            Authorization::Bearer(token) => request.bearer_auth(token),
+        }
+    }
+
+    // Obtém o token no servidor indicado pelo desafio (realm, service e scope)
+    async fn fetch_token(&self, params: &HashMap<String, String>) -> Result<String> {
+        let realm = params
+            .get("realm")
+            .ok_or_else(|| anyhow::anyhow!("Desafio Bearer sem realm"))?;
+
+        let query: Vec<(&str, &str)> = ["service", "scope"]
+            .into_iter()
+            .filter_map(|key| params.get(key).map(|value| (key, value.as_str())))
+            .collect();
+        let mut request = self.http.get(realm).query(&query);
+        if let Some((username, password)) = &self.credentials {
+            request = request.basic_auth(username, Some(password));
+        }
+
+        let response = request
+            .send()
+            .await
+            .context(format!("Falha ao obter token em {}", realm))?;
+        let response = ensure_success(response, "Falha ao obter token do registry").await?;
+        let token: TokenResponse = response
+            .json()
+            .await
+            .context("Resposta inválida do servidor de tokens")?;
+
+        token
+            .token
+            .or(token.access_token)
+            .ok_or_else(|| anyhow::anyhow!("Servidor de tokens não devolveu um token"))
+    }
+}
+
+// Converte respostas de erro usando as mensagens do formato de erro da API v2
+async fn ensure_success(response: Response, action: &str) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    let detail = serde_json::from_str::<RegistryErrors>(&body)
+        .ok()
+        .filter(|errors| !errors.errors.is_empty())
+        .map(|errors| {
+            errors
+                .errors
+                .iter()
+                .map(|error| format!("{}: {}", error.code, error.message))
+                .collect::<Vec<_>>()
+                .join("; ")
+        })
+        .unwrap_or(body);
+
+    Err(anyhow::anyhow!(
+        "{} (HTTP {}) {}",
+        action,
+        status.as_u16(),
+        detail.trim()
+    ))
+}
+
+fn content_digest(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get("Docker-Content-Digest")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+// Link: </v2/_catalog?last=repo&n=100>; rel="next"
+fn next_page(response: &Response) -> Option<String> {
+    let link = response.headers().get(header::LINK)?.to_str().ok()?;
+    link.split(',')
+        .find(|part| part.contains("rel=\"next\""))
+        .and_then(|part| {
+            let start = part.find('<')? + 1;
+            let end = part.find('>')?;
+            Some(part[start..end].to_string())
+        })
+}
+
+// WWW-Authenticate: Bearer realm="...",service="...",scope="repository:app:pull,push"
+fn parse_challenge(header: &str) -> (String, HashMap<String, String>) {
+    let header = header.trim();
+    let (scheme, rest) = header.split_once(' ').unwrap_or((header, ""));
+
+    let mut params = HashMap::new();
+    let mut chars = rest.chars().peekable();
+    loop {
+        let key: String = chars
+            .by_ref()
+            .skip_while(|c| *c == ',' || c.is_whitespace())
+            .take_while(|c| *c != '=')
+            .collect();
+        if key.is_empty() {
+            break;
+        }
+
+        let mut value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            let mut escaped = false;
+            for c in chars.by_ref() {
+                match c {
+                    '\\' if !escaped => escaped = true,
+                    '"' if !escaped => break,
+                    c => {
+                        escaped = false;
+                        value.push(c);
+                    }
+                }
+            }
+        } else {
+            value = chars.by_ref().take_while(|c| *c != ',').collect();
+        }
+
+        params.insert(key.trim().to_lowercase(), value.trim().to_string());
+    }
+
+    (scheme.to_lowercase(), params)
+}
@@ -0,0 +1,473 @@
+// Biblioteca de templates de containers salva no diretório de configuração do app
+use crate::docker::CreateContainerRequest;
+use crate::environment::{is_secret_key, SECRET_MASK};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+const TEMPLATES_FILE: &str = "container_templates.json";
+const BUNDLE_VERSION: u32 = 1;
+
+// Campos numéricos que podem receber placeholders (ex.: "host_port": "${PORT}")
+const NUMERIC_FIELDS: &[&str] = &[
+    "host_port",
+    "host_port_end",
+    "container_port",
+    "container_port_end",
+];
+
+// Variável usada nos placeholders de um template
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TemplateVariable {
+    pub name: String,
+    pub description: Option<String>,
+    pub default_value: Option<String>,
+}
+
+// Template de container: um CreateContainerRequest com placeholders ${VAR}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContainerTemplate {
+    pub name: String,
+    pub description: Option<String>,
+    pub request: Value,
+    pub variables: Vec<TemplateVariable>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+// Pacote de templates para importação/exportação
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateBundle {
+    pub version: u32,
+    pub exported_at: i64,
+    pub templates: Vec<ContainerTemplate>,
+}
+
+// Resultado da importação de um pacote
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateImportResult {
+    pub imported: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+pub struct TemplateStore {
+    path: PathBuf,
+}
+
+impl TemplateStore {
+    pub fn new(config_dir: &Path) -> Self {
+        TemplateStore {
+            path: config_dir.join(TEMPLATES_FILE),
+        }
+    }
+
+    // Lista os templates salvos, ordenados por nome
+    pub fn list(&self) -> Result<Vec<ContainerTemplate>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&self.path)
+            .context(format!("Falha ao ler templates: {}", self.path.display()))?;
+        let mut templates: Vec<ContainerTemplate> =
+            serde_json::from_str(&content).context("Arquivo de templates inválido")?;
+        templates.sort_by_key(|template| template.name.to_lowercase());
+
+        Ok(templates)
+    }
+
+    pub fn get(&self, name: &str) -> Result<ContainerTemplate> {
+        self.list()?
+            .into_iter()
+            .find(|template| template.name == name)
+            .ok_or_else(|| anyhow::anyhow!("Template '{}' não encontrado", name))
+    }
+
+    // Cria ou atualiza um template (pelo nome)
+    pub fn save(&self, mut template: ContainerTemplate) -> Result<ContainerTemplate> {
+        validate_template(&template)?;
+
+        let mut templates = self.list()?;
+        let now = chrono::Utc::now().timestamp();
+        template.updated_at = now;
+
+        match templates.iter_mut().find(|t| t.name == template.name) {
+            Some(existing) => {
+                // Segredos que voltaram mascarados do frontend mantêm o valor salvo
+                restore_masked_secrets(&mut template, existing);
+                template.created_at = existing.created_at;
+                *existing = template.clone();
+            }
+            None => {
+                template.created_at = now;
+                templates.push(template.clone());
+            }
+        }
+
+        self.write(&templates)?;
+        Ok(template)
+    }
+
+    pub fn delete(&self, name: &str) -> Result<()> {
+        let mut templates = self.list()?;
+        let count = templates.len();
+        templates.retain(|template| template.name != name);
+
+        if templates.len() == count {
+            return Err(anyhow::anyhow!("Template '{}' não encontrado", name));
+        }
+
+        self.write(&templates)
+    }
+
+    // Exporta os templates escolhidos (ou todos) para um arquivo de pacote
+    pub fn export(&self, names: &[String], destination: &Path) -> Result<usize> {
+        let templates: Vec<ContainerTemplate> = self
+            .list()?
+            .into_iter()
+            .filter(|template| names.is_empty() || names.contains(&template.name))
+            .collect();
+
+        if let Some(missing) = names
+            .iter()
+            .find(|name| !templates.iter().any(|t| &t.name == *name))
+        {
+            return Err(anyhow::anyhow!("Template '{}' não encontrado", missing));
+        }
+
+        let bundle = TemplateBundle {
+            version: BUNDLE_VERSION,
+            exported_at: chrono::Utc::now().timestamp(),
+            templates,
+        };
+        let content = serde_json::to_string_pretty(&bundle)?;
+        std::fs::write(destination, content).context(format!(
+            "Falha ao exportar templates: {}",
+            destination.display()
+        ))?;
+
+        Ok(bundle.templates.len())
+    }
+
+    // Importa um pacote; templates com nome existente só são substituídos com overwrite
+    pub fn import(&self, source: &Path, overwrite: bool) -> Result<TemplateImportResult> {
+        let content = std::fs::read_to_string(source)
+            .context(format!("Falha ao ler pacote: {}", source.display()))?;
+        let bundle: TemplateBundle =
+            serde_json::from_str(&content).context("Pacote de templates inválido")?;
+
+        if bundle.version > BUNDLE_VERSION {
+            return Err(anyhow::anyhow!(
+                "Versão do pacote não suportada: {}",
+                bundle.version
+            ));
+        }
+
+        for template in &bundle.templates {
+            validate_template(template)
+                .context(format!("Template '{}' inválido", template.name))?;
+        }
+
+        let mut templates = self.list()?;
+        let mut result = TemplateImportResult {
+            imported: Vec::new(),
+            skipped: Vec::new(),
+        };
+
+        for template in bundle.templates {
+            match templates.iter_mut().find(|t| t.name == template.name) {
+                Some(_) if !overwrite => result.skipped.push(template.name),
+                Some(existing) => {
+                    result.imported.push(template.name.clone());
+                    *existing = template;
+                }
+                None => {
+                    result.imported.push(template.name.clone());
+                    templates.push(template);
+                }
+            }
+        }
+
+        self.write(&templates)?;
+        Ok(result)
+    }
+
+    // Grava em arquivo temporário e renomeia, para não corromper o arquivo em caso de falha
+    fn write(&self, templates: &[ContainerTemplate]) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).context(format!(
+                "Falha ao criar diretório de configuração: {}",
+                parent.display()
+            ))?;
+        }
+
+        let temp_path = self.path.with_extension("json.tmp");
+        let content = serde_json::to_string_pretty(templates)?;
+        std::fs::write(&temp_path, content).context(format!(
+            "Falha ao salvar templates: {}",
+            temp_path.display()
+        ))?;
+        std::fs::rename(&temp_path, &self.path).context(format!(
+            "Falha ao salvar templates: {}",
+            self.path.display()
+        ))?;
+
+        Ok(())
+    }
+}
+
+// Monta o CreateContainerRequest substituindo os placeholders pelos valores informados
+pub fn render_template(
+    template: &ContainerTemplate,
+    values: &HashMap<String, String>,
+) -> Result<CreateContainerRequest> {
+    let mut resolved: HashMap<String, String> = HashMap::new();
+    let mut missing = Vec::new();
+
+    for name in template_placeholders(template) {
+        let value = values
+            .get(&name)
+            .filter(|v| !v.is_empty())
+            .cloned()
+            .or_else(|| {
+                template
+                    .variables
+                    .iter()
+                    .find(|variable| variable.name == name)
+                    .and_then(|variable| variable.default_value.clone())
+            });
+
+        match value {
+            Some(value) => {
+                resolved.insert(name, value);
+            }
+            None => missing.push(name),
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Variáveis sem valor: {}",
+            missing.join(", ")
+        ));
+    }
+
+    let request = substitute_value(&template.request, None, &resolved);
+    serde_json::from_value(request).context(format!(
+        "Template '{}' não gera uma configuração de container válida",
+        template.name
+    ))
+}
+
+// Nomes de todas as variáveis usadas no template (declaradas ou encontradas no request)
+pub fn template_placeholders(template: &ContainerTemplate) -> Vec<String> {
+    let mut names = BTreeSet::new();
+    collect_placeholders(&template.request, &mut names);
+    names.extend(template.variables.iter().map(|v| v.name.clone()));
+    names.into_iter().collect()
+}
+
+// Mascara valores sensíveis do ambiente e padrões de variáveis sensíveis
+// (placeholders como ${PASSWORD} não são segredos e continuam visíveis)
+pub fn mask_template_secrets(template: &mut ContainerTemplate) {
+    let should_mask = |value: &str| !value.is_empty() && !value.contains("${");
+
+    if let Some(environment) = template
+        .request
+        .get_mut("environment")
+        .and_then(Value::as_array_mut)
+    {
+        for var in environment {
+            let secret = var
+                .get("key")
+                .and_then(Value::as_str)
+                .is_some_and(is_secret_key);
+            let masked = var
+                .get("value")
+                .and_then(Value::as_str)
+                .is_some_and(should_mask);
+            if secret && masked {
+                var["value"] = Value::from(SECRET_MASK);
+            }
+        }
+    }
+
+    for variable in &mut template.variables {
+        if is_secret_key(&variable.name)
+            && variable.default_value.as_deref().is_some_and(should_mask)
+        {
+            variable.default_value = Some(SECRET_MASK.to_string());
+        }
+    }
+}
+
+fn restore_masked_secrets(template: &mut ContainerTemplate, existing: &ContainerTemplate) {
+    let saved_env = existing
+        .request
+        .get("environment")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    if let Some(environment) = template
+        .request
+        .get_mut("environment")
+        .and_then(Value::as_array_mut)
+    {
+        for var in environment {
+            if var.get("value").and_then(Value::as_str) != Some(SECRET_MASK) {
+                continue;
+            }
+            let saved = saved_env
+                .iter()
+                .find(|saved| saved.get("key") == var.get("key"))
+                .and_then(|saved| saved.get("value"))
+                .cloned();
+            if let Some(saved) = saved {
+                var["value"] = saved;
+            }
+        }
+    }
+
+    for variable in &mut template.variables {
+        if variable.default_value.as_deref() != Some(SECRET_MASK) {
+            continue;
+        }
+        variable.default_value = existing
+            .variables
+            .iter()
+            .find(|saved| saved.name == variable.name)
+            .and_then(|saved| saved.default_value.clone());
+    }
+}
+
+fn validate_template(template: &ContainerTemplate) -> Result<()> {
+    if template.name.trim().is_empty() {
+        return Err(anyhow::anyhow!("Nome do template é obrigatório"));
+    }
+
+    if !template.request.is_object() {
+        return Err(anyhow::anyhow!(
+            "A configuração do template deve ser um objeto"
+        ));
+    }
+
+    if let Some(variable) = template
+        .variables
+        .iter()
+        .find(|variable| !is_valid_variable_name(&variable.name))
+    {
+        return Err(anyhow::anyhow!(
+            "Nome de variável inválido: {}",
+            variable.name
+        ));
+    }
+
+    // Usa um valor fictício em cada placeholder para checar a estrutura
+    let sample: HashMap<String, String> = template_placeholders(template)
+        .into_iter()
+        .map(|name| (name, "1".to_string()))
+        .collect();
+    serde_json::from_value::<CreateContainerRequest>(substitute_value(
+        &template.request,
+        None,
+        &sample,
+    ))
+    .context("Configuração do template inválida")?;
+
+    Ok(())
+}
+
+fn is_valid_variable_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn collect_placeholders(value: &Value, names: &mut BTreeSet<String>) {
+    match value {
+        Value::String(text) => {
+            let mut rest = text.as_str();
+            while let Some(start) = rest.find("${") {
+                let after = &rest[start + 2..];
+                match after.find('}') {
+                    Some(end) => {
+                        if is_valid_variable_name(&after[..end]) {
+                            names.insert(after[..end].to_string());
+                        }
+                        rest = &after[end + 1..];
+                    }
+                    None => break,
+                }
+            }
+        }
+        Value::Array(items) => items
+            .iter()
+            .for_each(|item| collect_placeholders(item, names)),
+        Value::Object(map) => map
+            .values()
+            .for_each(|item| collect_placeholders(item, names)),
+        _ => {}
+    }
+}
+
+// Uma única passada da esquerda para a direita: o valor inserido não é reavaliado,
+// então um valor contendo ${OUTRA} fica literal
+fn substitute_placeholders(text: &str, values: &HashMap<String, String>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let name = &after[..end];
+        match values.get(name).filter(|_| is_valid_variable_name(name)) {
+            Some(replacement) => result.push_str(replacement),
+            None => result.push_str(&rest[start..start + 2 + end + 1]),
+        }
+        rest = &after[end + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+// Substitui ${VAR} em todas as strings; portas viram números após a substituição
+fn substitute_value(value: &Value, key: Option<&str>, values: &HashMap<String, String>) -> Value {
+    match value {
+        Value::String(text) => {
+            let result = substitute_placeholders(text, values);
+
+            if key.is_some_and(|key| NUMERIC_FIELDS.contains(&key)) {
+                // Valor vazio deixa a porta do host para o Docker escolher
+                if result.trim().is_empty() {
+                    return Value::Null;
+                }
+                if let Ok(number) = result.trim().parse::<u64>() {
+                    return Value::from(number);
+                }
+            }
+
+            Value::String(result)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| substitute_value(item, key, values))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), substitute_value(v, Some(k), values)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
@@ -3,8 +3,11 @@ import { invoke } from "@tauri-apps/api/core";
 import { FaTimes, FaPlus, FaTrash } from "react-icons/fa";
 
 interface PortMapping {
+  host_ip?: string;
   host_port: number;
+  host_port_end?: number;
   container_port: number;
+  container_port_end?: number;
   protocol: string;
 }
 
@@ -27,6 +30,8 @@ interface CreateContainerRequest {
   environment: EnvVar[];
   command?: string;
   restart_policy: string;
+  network?: string;
+  auto_remove?: boolean;
 }
 
 interface CreateContainerModalProps {
@@ -24,6 +24,7 @@ interface ContainerInfo {
   status: string;
   ports: number[];
   created: number;
+  health?: "starting" | "healthy" | "unhealthy" | null;
 }
 
 type FilterType = "all" | "running" | "stopped" | "paused";
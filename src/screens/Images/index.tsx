@@ -14,6 +14,13 @@ interface ImageInfo {
   in_use: boolean;
 }
 
+interface RemoveImageResult {
+  removed: boolean;
+  untagged: string[];
+  deleted: string[];
+  blocking_containers: { id: string; name: string; state: string }[];
+}
+
 type FilterType = "all" | "in_use" | "unused";
 
 export function Images() {
@@ -54,7 +61,16 @@ export function Images() {
     }
 
     try {
-      await invoke("docker_remove_image", { imageId });
+      const result: RemoveImageResult = await invoke("docker_remove_image", {
+        imageId,
+      });
+      if (!result.removed) {
+        const names = result.blocking_containers
+          .map((container) => `${container.name} (${container.state})`)
+          .join(", ");
+        showError(`Imagem em uso pelos containers: ${names}`);
+        return;
+      }
       showSuccess("Imagem removida com sucesso");
       await fetchImages();
     } catch (error) {